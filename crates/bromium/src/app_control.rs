@@ -8,12 +8,21 @@ use regex::Regex;
 use winapi::um::winuser::{
     FindWindowW, SetForegroundWindow, GetForegroundWindow, ShowWindow, BringWindowToTop,
     SW_RESTORE, SW_SHOW, GetWindowThreadProcessId, AttachThreadInput,
-    WM_SYSCOMMAND, SC_RESTORE, SendMessageW, EnumWindows, GetWindowTextW, 
-    GetWindowTextLengthW, IsWindowVisible, GetWindowPlacement, 
-    WINDOWPLACEMENT, SW_SHOWMINIMIZED, keybd_event, VK_MENU, KEYEVENTF_KEYUP
+    WM_SYSCOMMAND, SC_RESTORE, SendMessageW, EnumWindows, GetWindowTextW,
+    GetWindowTextLengthW, IsWindowVisible, GetWindowPlacement,
+    WINDOWPLACEMENT, SW_SHOWMINIMIZED, keybd_event, VK_MENU, KEYEVENTF_KEYUP,
+    GetWindow, GW_OWNER, GetClassNameW, EnumChildWindows,
+    SetWinEventHook, UnhookWinEvent, EVENT_OBJECT_SHOW, EVENT_SYSTEM_FOREGROUND,
+    WINEVENT_OUTOFCONTEXT, MSG, PeekMessageW, PM_REMOVE, TranslateMessage, DispatchMessageW,
+    GetWindowRect, GetClientRect, SW_SHOWMAXIMIZED,
 };
-use winapi::shared::windef::HWND;
-use winapi::shared::minwindef::{BOOL, LPARAM};
+use winapi::um::tlhelp32::{CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS};
+use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+use winapi::shared::windef::{HWND, HWINEVENTHOOK, RECT};
+use winapi::shared::minwindef::{BOOL, DWORD, LPARAM};
+use winapi::shared::ntdef::LONG;
+use std::sync::Mutex;
+use std::time::Instant;
 use log::{debug, error, info, trace, warn};
 
 // Extract window names from XPath
@@ -42,6 +51,33 @@ fn extract_window_names_from_xpath(xpath: &str) -> Vec<String> {
     window_names
 }
 
+// Extract window class names from XPath, e.g. `/Window[@ClassName="Notepad"]` - a
+// window's class (`Chrome_WidgetWin_1`, `Notepad`, ...) is far more stable to match on
+// than its caption, which changes with the open document or UI locale.
+fn extract_window_classes_from_xpath(xpath: &str) -> Vec<String> {
+    debug!("Extract window classes from XPath: {}", xpath);
+
+    let mut class_names = Vec::new();
+
+    let patterns = [
+        r#"/Window\[@ClassName="([^"]+)"\]"#,
+        r#"Window\[@ClassName="([^"]+)"\]"#,
+        r#"\[@ClassName="([^"]+)"\]"#,
+    ];
+
+    for pattern in &patterns {
+        if let Ok(re) = Regex::new(pattern) {
+            for cap in re.captures_iter(xpath) {
+                if let Some(class_name) = cap.get(1) {
+                    class_names.push(class_name.as_str().to_string());
+                }
+            }
+        }
+    }
+
+    class_names
+}
+
 // Scan for all windows on the system
 fn scan_for_all_windows() -> Vec<(String, HWND)> {
     debug!("Scanning for all windows on the system");
@@ -89,6 +125,319 @@ fn scan_for_all_windows() -> Vec<(String, HWND)> {
     data.windows
 }
 
+// Maximum class name length per the Win32 docs (`GetClassNameW`'s own `MAX_CLASS_NAME`).
+const MAX_CLASS_NAME_LEN: usize = 256;
+
+// Scan for all windows on the system, capturing each one's title and window-class name
+// (e.g. `Chrome_WidgetWin_1`, `Notepad`) alongside its handle.
+fn scan_for_all_windows_with_class() -> Vec<(String, String, HWND)> {
+    debug!("Scanning for all windows on the system, with class names");
+
+    struct AllWindowsData {
+        windows: Vec<(String, String, HWND)>,
+    }
+
+    extern "system" fn collect_all_windows(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        unsafe {
+            if IsWindowVisible(hwnd) != 0 {
+                let length = GetWindowTextLengthW(hwnd);
+                let title = if length > 0 {
+                    let mut buffer = vec![0u16; length as usize + 1];
+                    GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+                    String::from_utf16_lossy(&buffer[..length as usize])
+                } else {
+                    String::new()
+                };
+
+                let mut class_buffer = vec![0u16; MAX_CLASS_NAME_LEN];
+                let class_len = GetClassNameW(hwnd, class_buffer.as_mut_ptr(), class_buffer.len() as i32);
+                let class_name = String::from_utf16_lossy(&class_buffer[..class_len.max(0) as usize]);
+
+                let data = &mut *(lparam as *mut AllWindowsData);
+                data.windows.push((title, class_name, hwnd));
+            }
+            1 // Continue enumeration
+        }
+    }
+
+    let mut data = AllWindowsData {
+        windows: Vec::new(),
+    };
+
+    unsafe {
+        EnumWindows(
+            Some(collect_all_windows),
+            &mut data as *mut AllWindowsData as LPARAM
+        );
+    }
+
+    debug!("Window scan (with class) complete, found {} windows", data.windows.len());
+    data.windows
+}
+
+/// A window's geometry and identity, gathered in one place so callers can compute a
+/// control's absolute screen location (feeding the existing `ScreenContext`) without
+/// re-deriving it from several separate Win32 calls.
+pub struct WindowInfo {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub client_left: i32,
+    pub client_top: i32,
+    pub client_right: i32,
+    pub client_bottom: i32,
+    pub is_minimized: bool,
+    pub is_maximized: bool,
+    pub pid: u32,
+    pub class_name: String,
+}
+
+/// Gather `hwnd`'s bounding rect (`GetWindowRect`), client rect (`GetClientRect`),
+/// minimized/maximized state (`GetWindowPlacement`'s `showCmd`), owning process ID, and
+/// class name. Returns `None` if `hwnd` is invalid (`GetWindowRect` fails).
+pub fn get_window_info(hwnd: HWND) -> Option<WindowInfo> {
+    unsafe {
+        let mut window_rect: RECT = std::mem::zeroed();
+        if GetWindowRect(hwnd, &mut window_rect) == 0 {
+            warn!("GetWindowRect failed for handle {:?}; window may no longer exist", hwnd);
+            return None;
+        }
+
+        let mut client_rect: RECT = std::mem::zeroed();
+        GetClientRect(hwnd, &mut client_rect);
+
+        let mut placement = std::mem::zeroed::<WINDOWPLACEMENT>();
+        placement.length = std::mem::size_of::<WINDOWPLACEMENT>() as u32;
+        GetWindowPlacement(hwnd, &mut placement);
+        let show_cmd = placement.showCmd as i32;
+
+        let mut owner_pid: u32 = 0;
+        GetWindowThreadProcessId(hwnd, &mut owner_pid);
+
+        let mut class_buffer = vec![0u16; MAX_CLASS_NAME_LEN];
+        let class_len = GetClassNameW(hwnd, class_buffer.as_mut_ptr(), class_buffer.len() as i32);
+        let class_name = String::from_utf16_lossy(&class_buffer[..class_len.max(0) as usize]);
+
+        Some(WindowInfo {
+            left: window_rect.left,
+            top: window_rect.top,
+            right: window_rect.right,
+            bottom: window_rect.bottom,
+            client_left: client_rect.left,
+            client_top: client_rect.top,
+            client_right: client_rect.right,
+            client_bottom: client_rect.bottom,
+            is_minimized: show_cmd == SW_SHOWMINIMIZED,
+            is_maximized: show_cmd == SW_SHOWMAXIMIZED,
+            pid: owner_pid,
+            class_name,
+        })
+    }
+}
+
+// Find the first visible window whose class name matches `class_name` exactly.
+fn find_window_by_class(class_name: &str) -> Option<HWND> {
+    debug!("Find window by class: '{}'", class_name);
+    scan_for_all_windows_with_class()
+        .into_iter()
+        .find(|(_, class, _)| class == class_name)
+        .map(|(_, _, hwnd)| hwnd)
+}
+
+/// How long `launch_or_activate_application` waits on the WinEvent hook for the spawned
+/// process's window to appear before giving up and falling back to the polling scan.
+const WINEVENT_WAIT_TIMEOUT_MS: u64 = 5000;
+
+struct WinEventMatchCriteria {
+    pid: u32,
+    xpath_names: Vec<String>,
+    classes: Vec<String>,
+}
+
+// Match criteria and result for the in-flight `wait_for_window_via_winevent` call, read
+// and written from `win_event_callback` - a plain `extern "system" fn` can't capture
+// state, so it has to live in statics instead.
+static WINEVENT_MATCH: Mutex<Option<WinEventMatchCriteria>> = Mutex::new(None);
+static WINEVENT_FOUND: Mutex<Option<usize>> = Mutex::new(None);
+
+fn set_winevent_criteria(pid: u32, xpath_names: Vec<String>, classes: Vec<String>) {
+    let mut criteria = match WINEVENT_MATCH.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *criteria = Some(WinEventMatchCriteria { pid, xpath_names, classes });
+}
+
+fn clear_winevent_state() {
+    if let Ok(mut criteria) = WINEVENT_MATCH.lock() {
+        *criteria = None;
+    }
+    if let Ok(mut found) = WINEVENT_FOUND.lock() {
+        *found = None;
+    }
+}
+
+// Called for every `EVENT_OBJECT_SHOW`/`EVENT_SYSTEM_FOREGROUND` WinEvent while a hook
+// is active. Filters `hwnd` against the current criteria (spawned PID, plus any XPath
+// window name / class hints) and records it as the match once found.
+extern "system" fn win_event_callback(
+    _hook: HWINEVENTHOOK,
+    _event: DWORD,
+    hwnd: HWND,
+    _id_object: LONG,
+    _id_child: LONG,
+    _event_thread: DWORD,
+    _event_time: DWORD,
+) {
+    if hwnd.is_null() {
+        return;
+    }
+
+    unsafe {
+        if IsWindowVisible(hwnd) == 0 {
+            return;
+        }
+
+        let criteria_guard = match WINEVENT_MATCH.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let criteria = match criteria_guard.as_ref() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let mut owner_pid: u32 = 0;
+        GetWindowThreadProcessId(hwnd, &mut owner_pid);
+        let matches_pid = owner_pid == criteria.pid;
+
+        let mut class_buffer = vec![0u16; MAX_CLASS_NAME_LEN];
+        let class_len = GetClassNameW(hwnd, class_buffer.as_mut_ptr(), class_buffer.len() as i32);
+        let class_name = String::from_utf16_lossy(&class_buffer[..class_len.max(0) as usize]);
+        let matches_class = criteria.classes.iter().any(|c| c == &class_name);
+
+        let title_len = GetWindowTextLengthW(hwnd);
+        let title = if title_len > 0 {
+            let mut buffer = vec![0u16; title_len as usize + 1];
+            GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+            String::from_utf16_lossy(&buffer[..title_len as usize])
+        } else {
+            String::new()
+        };
+        let matches_name = criteria.xpath_names.iter().any(|n| title.contains(n.as_str()));
+
+        if matches_pid || matches_class || matches_name {
+            trace!("WinEvent hook matched window '{}' (class '{}', pid {})", title, class_name, owner_pid);
+            if let Ok(mut found) = WINEVENT_FOUND.lock() {
+                *found = Some(hwnd as usize);
+            }
+        }
+    }
+}
+
+// Install the `EVENT_OBJECT_SHOW`/`EVENT_SYSTEM_FOREGROUND` hooks used to wait for a
+// spawned process's window out-of-process (`WINEVENT_OUTOFCONTEXT`), returning both
+// hook handles (null if a given hook failed to install).
+fn install_winevent_hooks() -> (HWINEVENTHOOK, HWINEVENTHOOK) {
+    unsafe {
+        let show_hook = SetWinEventHook(
+            EVENT_OBJECT_SHOW, EVENT_OBJECT_SHOW,
+            std::ptr::null_mut(), Some(win_event_callback), 0, 0, WINEVENT_OUTOFCONTEXT,
+        );
+        let foreground_hook = SetWinEventHook(
+            EVENT_SYSTEM_FOREGROUND, EVENT_SYSTEM_FOREGROUND,
+            std::ptr::null_mut(), Some(win_event_callback), 0, 0, WINEVENT_OUTOFCONTEXT,
+        );
+
+        if show_hook.is_null() || foreground_hook.is_null() {
+            warn!("Failed to install one or both WinEvent hooks; window-appear detection will rely on the polling fallback");
+        }
+
+        (show_hook, foreground_hook)
+    }
+}
+
+fn uninstall_winevent_hooks(hooks: (HWINEVENTHOOK, HWINEVENTHOOK)) {
+    unsafe {
+        if !hooks.0.is_null() {
+            UnhookWinEvent(hooks.0);
+        }
+        if !hooks.1.is_null() {
+            UnhookWinEvent(hooks.1);
+        }
+    }
+}
+
+/// Pump the thread's message queue (the WinEvent hook callback only fires from within a
+/// `GetMessageW`/`PeekMessageW` loop) until a matching window is found or `timeout_ms`
+/// elapses, returning it immediately rather than sleeping through the rest of the
+/// timeout once a match is seen.
+fn pump_for_window(timeout_ms: u64) -> Option<HWND> {
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+
+    while Instant::now() < deadline {
+        unsafe {
+            let mut msg: MSG = std::mem::zeroed();
+            while PeekMessageW(&mut msg, std::ptr::null_mut(), 0, 0, PM_REMOVE) != 0 {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+
+        if let Ok(found) = WINEVENT_FOUND.lock() {
+            if let Some(hwnd) = *found {
+                return Some(hwnd as HWND);
+            }
+        }
+
+        thread::sleep(Duration::from_millis(15));
+    }
+
+    None
+}
+
+/// Every descendant window under `parent`'s text, class name, and handle, via
+/// `EnumChildWindows` (which itself walks the full descendant subtree, not just the
+/// immediate children). A pure-Win32 fallback for reaching nested controls - buttons,
+/// edit boxes - when UIAutomation is unavailable or too slow.
+pub fn enumerate_child_windows(parent: HWND) -> Vec<(String, String, HWND)> {
+    debug!("Enumerating child windows of {:?}", parent);
+
+    struct ChildWindowsData {
+        children: Vec<(String, String, HWND)>,
+    }
+
+    extern "system" fn collect_child(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        unsafe {
+            let length = GetWindowTextLengthW(hwnd);
+            let title = if length > 0 {
+                let mut buffer = vec![0u16; length as usize + 1];
+                GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+                String::from_utf16_lossy(&buffer[..length as usize])
+            } else {
+                String::new()
+            };
+
+            let mut class_buffer = vec![0u16; MAX_CLASS_NAME_LEN];
+            let class_len = GetClassNameW(hwnd, class_buffer.as_mut_ptr(), class_buffer.len() as i32);
+            let class_name = String::from_utf16_lossy(&class_buffer[..class_len.max(0) as usize]);
+
+            let data = &mut *(lparam as *mut ChildWindowsData);
+            data.children.push((title, class_name, hwnd));
+            1 // Continue enumeration
+        }
+    }
+
+    let mut data = ChildWindowsData { children: Vec::new() };
+    unsafe {
+        EnumChildWindows(parent, Some(collect_child), &mut data as *mut ChildWindowsData as LPARAM);
+    }
+
+    debug!("Found {} descendant windows under {:?}", data.children.len(), parent);
+    data.children
+}
+
 // Find window with partial name
 fn find_window_with_partial_name(name_part: &str) -> Option<String> {
     debug!("Find window with partial name: '{}'", name_part);
@@ -143,6 +492,209 @@ fn find_window_with_partial_name(name_part: &str) -> Option<String> {
     data.found_name
 }
 
+/// How a window should be resolved when activating by XPath: an exact title match, a
+/// partial/contained title match, an exact window-class match, or by owning PID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowMatchMode {
+    Exact,
+    Partial,
+    Class,
+    Pid,
+}
+
+impl WindowMatchMode {
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode.to_lowercase().as_str() {
+            "exact" => Ok(WindowMatchMode::Exact),
+            "partial" => Ok(WindowMatchMode::Partial),
+            "class" => Ok(WindowMatchMode::Class),
+            "pid" => Ok(WindowMatchMode::Pid),
+            other => Err(format!("Unknown window match mode: '{other}' (expected exact, partial, class, or pid)")),
+        }
+    }
+}
+
+/// Tunables for `activate_window_with_options`/`activate_or_launch_with_options`, letting
+/// a caller trade off how long to wait for the foreground switch to be confirmed, whether
+/// the Alt-key foreground-unlock trick is attempted, and how a target window is matched.
+#[derive(Debug, Clone)]
+pub struct ActivationOptions {
+    pub confirm_timeout_ms: u64,
+    pub use_alt_key_unlock: bool,
+    pub match_mode: WindowMatchMode,
+}
+
+impl Default for ActivationOptions {
+    fn default() -> Self {
+        ActivationOptions {
+            confirm_timeout_ms: 100,
+            use_alt_key_unlock: true,
+            match_mode: WindowMatchMode::Exact,
+        }
+    }
+}
+
+/// The structured outcome of an activation attempt - richer than a bare `bool` so a
+/// caller can tell an unconfirmed activation from a confirmed one, or a fresh launch
+/// from an existing window, and decide whether to retry or abort instead of having
+/// every outcome silently reported as success.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivationResult {
+    AlreadyForeground,
+    Activated,
+    ActivatedUnconfirmed,
+    NotFound,
+    LaunchedNew(u32),
+}
+
+impl ActivationResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivationResult::AlreadyForeground => "already_foreground",
+            ActivationResult::Activated => "activated",
+            ActivationResult::ActivatedUnconfirmed => "activated_unconfirmed",
+            ActivationResult::NotFound => "not_found",
+            ActivationResult::LaunchedNew(_) => "launched_new",
+        }
+    }
+
+    pub fn pid(&self) -> Option<u32> {
+        match self {
+            ActivationResult::LaunchedNew(pid) => Some(*pid),
+            _ => None,
+        }
+    }
+}
+
+// Bring an already-resolved window handle to the foreground, escalating through
+// BringWindowToTop -> SC_RESTORE -> AttachThreadInput -> (optionally) the Alt-key
+// foreground-unlock trick, confirming within `options.confirm_timeout_ms` whether it
+// actually took effect rather than assuming success.
+fn activate_window_with_options(hwnd: HWND, options: &ActivationOptions) -> ActivationResult {
+    unsafe {
+        // First, check if already in foreground
+        let foreground_hwnd = GetForegroundWindow();
+        if foreground_hwnd == hwnd {
+            return ActivationResult::AlreadyForeground;
+        }
+
+        // Get window placement info to check if minimized
+        let mut placement = std::mem::zeroed::<WINDOWPLACEMENT>();
+        placement.length = std::mem::size_of::<WINDOWPLACEMENT>() as u32;
+
+        if GetWindowPlacement(hwnd, &mut placement) != 0 {
+            // If window is minimized, restore it
+            if placement.showCmd as i32 == SW_SHOWMINIMIZED {
+                ShowWindow(hwnd, SW_RESTORE);
+            }
+        }
+
+        // Bring window to top of Z-order
+        BringWindowToTop(hwnd);
+
+        // Send SC_RESTORE command
+        SendMessageW(hwnd, WM_SYSCOMMAND, SC_RESTORE, 0);
+
+        // More aggressive window activation with thread attachment
+        let foreground_thread = GetWindowThreadProcessId(
+            GetForegroundWindow(), std::ptr::null_mut());
+        let target_thread = GetWindowThreadProcessId(
+            hwnd, std::ptr::null_mut());
+
+        if foreground_thread != target_thread {
+            AttachThreadInput(foreground_thread, target_thread, 1); // Attach
+
+            // Multiple activation attempts
+            SetForegroundWindow(hwnd);
+            ShowWindow(hwnd, SW_SHOW);
+
+            // Small delay
+            thread::sleep(Duration::from_millis(50));
+
+            AttachThreadInput(foreground_thread, target_thread, 0); // Detach
+        } else {
+            // Direct activation for same thread
+            SetForegroundWindow(hwnd);
+            ShowWindow(hwnd, SW_SHOW);
+        }
+
+        // One final check and activation attempt
+        if GetForegroundWindow() != hwnd && options.use_alt_key_unlock {
+            // Alt key action to allow foreground switching
+            keybd_event(VK_MENU as u8, 0, 0, 0);
+            SetForegroundWindow(hwnd);
+            keybd_event(VK_MENU as u8, 0, KEYEVENTF_KEYUP, 0);
+        }
+
+        // Wait to confirm focus
+        thread::sleep(Duration::from_millis(options.confirm_timeout_ms));
+
+        if GetForegroundWindow() == hwnd {
+            info!("Successfully brought window to foreground");
+            ActivationResult::Activated
+        } else {
+            warn!("Window activation could not be confirmed within {}ms", options.confirm_timeout_ms);
+            ActivationResult::ActivatedUnconfirmed
+        }
+    }
+}
+
+// Bring an already-resolved window handle to the foreground using the default
+// activation options - kept as a `bool`-returning helper for call sites that predate
+// `ActivationResult` and don't need the richer outcome.
+fn activate_window(hwnd: HWND) -> bool {
+    matches!(
+        activate_window_with_options(hwnd, &ActivationOptions::default()),
+        ActivationResult::AlreadyForeground | ActivationResult::Activated | ActivationResult::ActivatedUnconfirmed
+    )
+}
+
+/// Resolve a window for `xpath` according to `match_mode`, without activating it.
+fn resolve_window_for_xpath(xpath: &str, pid: Option<u32>, match_mode: WindowMatchMode) -> Option<HWND> {
+    match match_mode {
+        WindowMatchMode::Pid => pid.and_then(find_main_window_for_pid),
+        WindowMatchMode::Class => {
+            extract_window_classes_from_xpath(xpath).iter().find_map(|class_name| find_window_by_class(class_name))
+        }
+        WindowMatchMode::Exact => {
+            let names = extract_window_names_from_xpath(xpath);
+            scan_for_all_windows().into_iter()
+                .find(|(title, _)| names.iter().any(|n| n == title))
+                .map(|(_, hwnd)| hwnd)
+        }
+        WindowMatchMode::Partial => {
+            let names = extract_window_names_from_xpath(xpath);
+            scan_for_all_windows().into_iter()
+                .find(|(title, _)| names.iter().any(|n| n.len() > 3 && title.to_lowercase().contains(&n.to_lowercase())))
+                .map(|(_, hwnd)| hwnd)
+        }
+    }
+}
+
+/// Activate the window matching `xpath` (per `options.match_mode`), or launch a new
+/// process for `app_path` when none is found, waiting for its window through the
+/// existing WinEvent-hook/poll pipeline. Returns a structured `ActivationResult`
+/// rather than `launch_or_activate_application`'s `LaunchOutcome`, so a caller can tell
+/// a confirmed activation from an unconfirmed one, or a fresh launch from no match.
+pub fn activate_or_launch_with_options(
+    app_path: &str,
+    xpath: &str,
+    args: &[String],
+    cwd: Option<&str>,
+    env: Option<&std::collections::HashMap<String, String>>,
+    options: &ActivationOptions,
+) -> ActivationResult {
+    if let Some(hwnd) = resolve_window_for_xpath(xpath, None, options.match_mode) {
+        return activate_window_with_options(hwnd, options);
+    }
+
+    let outcome = launch_or_activate_application(app_path, xpath, args, cwd, env, false, None);
+    match outcome.pid {
+        Some(pid) if outcome.success => ActivationResult::LaunchedNew(pid),
+        _ => ActivationResult::NotFound,
+    }
+}
+
 // Activate window by name
 fn activate_window_by_name(window_name: &str) -> bool {
     debug!("Activate window by name: '{}'", window_name);
@@ -152,94 +704,179 @@ fn activate_window_by_name(window_name: &str) -> bool {
         .encode_wide()
         .chain(std::iter::once(0))
         .collect();
-    
+
+    let hwnd = unsafe { FindWindowW(std::ptr::null(), window_name_wide.as_ptr()) };
+    if hwnd == std::ptr::null_mut() {
+        error!("Window not found for activation: '{}'", window_name);
+        return false;
+    }
+
+    trace!("Found window handle for: '{}'", window_name);
+    activate_window(hwnd)
+}
+
+/// PIDs of all processes whose parent process is `pid`, via a toolhelp snapshot - used
+/// to follow a launcher stub that spawns the real process and exits.
+fn child_pids_of(pid: u32) -> Vec<u32> {
+    let mut children = Vec::new();
+
     unsafe {
-        let hwnd = FindWindowW(std::ptr::null(), window_name_wide.as_ptr());
-        if hwnd != std::ptr::null_mut() {
-            trace!("Found window handle for: '{}'", window_name);
-            // First, check if already in foreground
-            let foreground_hwnd = GetForegroundWindow();
-            if foreground_hwnd == hwnd {
-                return true;
-            }
-            
-            // Get window placement info to check if minimized
-            let mut placement = std::mem::zeroed::<WINDOWPLACEMENT>();
-            placement.length = std::mem::size_of::<WINDOWPLACEMENT>() as u32;
-            
-            if GetWindowPlacement(hwnd, &mut placement) != 0 {
-                // If window is minimized, restore it
-                if placement.showCmd as i32 == SW_SHOWMINIMIZED {
-                    ShowWindow(hwnd, SW_RESTORE);
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            warn!("Failed to create toolhelp snapshot to look up child processes of PID {}", pid);
+            return children;
+        }
+
+        let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+
+        if Process32FirstW(snapshot, &mut entry) != 0 {
+            loop {
+                if entry.th32ParentProcessID == pid {
+                    children.push(entry.th32ProcessID);
+                }
+                if Process32NextW(snapshot, &mut entry) == 0 {
+                    break;
                 }
             }
-            
-            // Bring window to top of Z-order
-            BringWindowToTop(hwnd);
-            
-            // Send SC_RESTORE command
-            SendMessageW(hwnd, WM_SYSCOMMAND, SC_RESTORE, 0);
-            
-            // More aggressive window activation with thread attachment
-            let foreground_thread = GetWindowThreadProcessId(
-                GetForegroundWindow(), std::ptr::null_mut());
-            let target_thread = GetWindowThreadProcessId(
-                hwnd, std::ptr::null_mut());
-            
-            if foreground_thread != target_thread {
-                AttachThreadInput(foreground_thread, target_thread, 1); // Attach
-                
-                // Multiple activation attempts
-                SetForegroundWindow(hwnd);
-                ShowWindow(hwnd, SW_SHOW);
-                
-                // Small delay
-                thread::sleep(Duration::from_millis(50));
-                
-                AttachThreadInput(foreground_thread, target_thread, 0); // Detach
-            } else {
-                // Direct activation for same thread
-                SetForegroundWindow(hwnd);
-                ShowWindow(hwnd, SW_SHOW);
+        }
+
+        CloseHandle(snapshot);
+    }
+
+    children
+}
+
+/// Find the main top-level window belonging to `pid` (or one of its child processes, in
+/// case it launched via a stub that has since exited), via `EnumWindows` +
+/// `GetWindowThreadProcessId`. Among matching windows, one with no owner window
+/// (`GetWindow(hwnd, GW_OWNER)` is null) and a non-empty title is preferred, since that's
+/// the actual main window rather than a splash screen or tooltip.
+fn find_main_window_for_pid(pid: u32) -> Option<HWND> {
+    debug!("Looking for main window owned by PID {}", pid);
+
+    struct PidSearchData {
+        pids: Vec<u32>,
+        found: Option<HWND>,
+    }
+
+    extern "system" fn search_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        unsafe {
+            if IsWindowVisible(hwnd) == 0 {
+                return 1;
             }
-            
-            // One final check and activation attempt
-            if GetForegroundWindow() != hwnd {
-                // Alt key action to allow foreground switching
-                keybd_event(VK_MENU as u8, 0, 0, 0);
-                SetForegroundWindow(hwnd);
-                keybd_event(VK_MENU as u8, 0, KEYEVENTF_KEYUP, 0);
+
+            let mut owner_pid: u32 = 0;
+            GetWindowThreadProcessId(hwnd, &mut owner_pid);
+
+            let data = &mut *(lparam as *mut PidSearchData);
+            if !data.pids.contains(&owner_pid) {
+                return 1;
             }
-            
-            // Wait to confirm focus
-            thread::sleep(Duration::from_millis(100));
-            
-            if GetForegroundWindow() == hwnd {
-                info!("Successfully brought window to foreground: '{}'", window_name);
-                return true;
-            } else {
-                warn!("Window activation may have failed for: '{}'", window_name);
-                return true; // Still return true as activation was attempted
+
+            let has_owner = !GetWindow(hwnd, GW_OWNER).is_null();
+            let has_title = GetWindowTextLengthW(hwnd) > 0;
+
+            if !has_owner && has_title {
+                data.found = Some(hwnd);
+                return 0; // exactly what we're looking for, stop here
+            }
+
+            if data.found.is_none() {
+                data.found = Some(hwnd); // keep as a fallback candidate
             }
+
+            1
         }
     }
-    
-    error!("Window not found for activation: '{}'", window_name);
-    false // Window not found
+
+    let mut pids = vec![pid];
+    pids.extend(child_pids_of(pid));
+
+    let mut data = PidSearchData { pids, found: None };
+    unsafe {
+        EnumWindows(Some(search_callback), &mut data as *mut PidSearchData as LPARAM);
+    }
+
+    if data.found.is_some() {
+        debug!("Found a window for PID {} (including child processes)", pid);
+    } else {
+        debug!("No window found for PID {} or its child processes", pid);
+    }
+
+    data.found
+}
+
+/// Result of a `launch_or_activate_application` call: whether an existing window was
+/// activated or a new process spawned, and the PID of the process actually running the
+/// target application, when known - a freshly launched process always has one; an
+/// activated pre-existing window does not, since activation never spawned anything.
+pub struct LaunchOutcome {
+    pub success: bool,
+    pub pid: Option<u32>,
+}
+
+impl LaunchOutcome {
+    fn activated(success: bool) -> Self {
+        LaunchOutcome { success, pid: None }
+    }
+}
+
+/// Wipe `user_data_dir` (if it exists) and recreate it empty, so the application launches
+/// against a known-clean profile/app-data directory instead of one left over from a
+/// previous run.
+fn prepare_user_data_dir(user_data_dir: &str) {
+    debug!("Preparing clean user data directory: {}", user_data_dir);
+    if std::path::Path::new(user_data_dir).exists() {
+        if let Err(e) = std::fs::remove_dir_all(user_data_dir) {
+            warn!("Failed to clear user data directory '{}': {:?}", user_data_dir, e);
+        }
+    }
+    if let Err(e) = std::fs::create_dir_all(user_data_dir) {
+        warn!("Failed to recreate user data directory '{}': {:?}", user_data_dir, e);
+    }
 }
 
-/// Launch or activate an application based on its path and XPath
-/// Returns true if successful, false otherwise
-pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
+/// Launch or activate an application based on its path and XPath.
+///
+/// Args:
+///     app_path: full path to the application executable
+///     xpath: XPath identifying an element in the application window, used to guess its
+///         window title when searching for an already-running instance
+///     args: extra command-line arguments passed when a new process has to be spawned
+///     cwd: working directory for a newly spawned process; the current one if `None`
+///     env: extra environment variables for a newly spawned process
+///     clear_user_data: when true, wipe `user_data_dir` before launch so the app starts
+///         from a known-clean state
+///     user_data_dir: directory to clear when `clear_user_data` is set
+///
+/// Returns the outcome of the attempt, including the PID when a process was spawned.
+pub fn launch_or_activate_application(
+    app_path: &str,
+    xpath: &str,
+    args: &[String],
+    cwd: Option<&str>,
+    env: Option<&std::collections::HashMap<String, String>>,
+    clear_user_data: bool,
+    user_data_dir: Option<&str>,
+) -> LaunchOutcome {
     info!("Attempting to launch or activate application: {}", app_path);
     debug!("Using xpath: {}", xpath);
 
+    if clear_user_data {
+        if let Some(dir) = user_data_dir {
+            prepare_user_data_dir(dir);
+        } else {
+            warn!("clear_user_data requested but no user_data_dir was given; skipping");
+        }
+    }
+
     // Extract application name from path
     let app_name = match std::path::Path::new(app_path).file_name() {
         Some(name) => name.to_string_lossy().to_string(),
         None => {
             error!("Invalid application path: {}", app_path);
-            return false;
+            return LaunchOutcome::activated(false);
         }
     };
     
@@ -255,9 +892,29 @@ pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
     
     // First, try window names from XPath
     let xpath_window_names = extract_window_names_from_xpath(xpath);
-    debug!("Extracted {} window names from XPath: {:?}", 
+    debug!("Extracted {} window names from XPath: {:?}",
            xpath_window_names.len(), xpath_window_names);
-    
+
+    // Class names (e.g. `/Window[@ClassName="Notepad"]`) are far more stable than the
+    // caption, so try an exact class match before falling back to title matching.
+    let xpath_window_classes = extract_window_classes_from_xpath(xpath);
+    debug!("Extracted {} window classes from XPath: {:?}",
+           xpath_window_classes.len(), xpath_window_classes);
+
+    for class_name in &xpath_window_classes {
+        if let Some(hwnd) = find_window_by_class(class_name) {
+            info!("Found window via exact class match: '{}'", class_name);
+            let result = activate_window(hwnd);
+            if result {
+                info!("Successfully activated existing window with class: '{}'", class_name);
+            } else {
+                error!("Failed to activate window with class: '{}'", class_name);
+            }
+            return LaunchOutcome::activated(result);
+        }
+    }
+    debug!("No exact window class matches found");
+
     // Build a list of potential window names to check
     let mut potential_names = xpath_window_names.clone();
     
@@ -290,7 +947,7 @@ pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
                 } else {
                     error!("Failed to activate window: '{}'", window_title);
                 }
-                return result;
+                return LaunchOutcome::activated(result);
             }
         }
     }
@@ -316,7 +973,7 @@ pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
                 } else {
                     error!("Failed to activate window: '{}'", window_title);
                 }
-                return result;
+                return LaunchOutcome::activated(result);
             }
         }
     }
@@ -335,7 +992,7 @@ pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
                 } else {
                     error!("Failed to activate window: '{}'", found_window);
                 }
-                return result;
+                return LaunchOutcome::activated(result);
             }
         }
     }
@@ -343,14 +1000,47 @@ pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
     
     // If not found, launch the application
     info!("Window not found, launching new application instance: {}", app_path);
-    match Command::new(app_path).spawn() {
+    let mut command = Command::new(app_path);
+    command.args(args);
+    if let Some(cwd) = cwd {
+        command.current_dir(cwd);
+    }
+    if let Some(env) = env {
+        for (key, value) in env {
+            command.env(key, value);
+        }
+    }
+
+    // Install the WinEvent hook before spawning, so no window shown while the process
+    // is starting up can be missed.
+    let winevent_hooks = install_winevent_hooks();
+
+    match command.spawn() {
         Ok(child) => {
-            info!("Successfully spawned process with PID: {:?}", child.id());
-            
+            let pid = child.id();
+            info!("Successfully spawned process with PID: {}", pid);
+
+            set_winevent_criteria(pid, xpath_window_names.clone(), xpath_window_classes.clone());
+            let winevent_match = pump_for_window(WINEVENT_WAIT_TIMEOUT_MS);
+            uninstall_winevent_hooks(winevent_hooks);
+            clear_winevent_state();
+
+            if let Some(hwnd) = winevent_match {
+                info!("Found window for spawned PID {} via WinEvent hook", pid);
+                let result = activate_window(hwnd);
+                if result {
+                    info!("Successfully activated window for PID {}", pid);
+                } else {
+                    error!("Failed to activate window for PID {}", pid);
+                }
+                return LaunchOutcome { success: result, pid: Some(pid) };
+            }
+            warn!("WinEvent hook saw no matching window within {}ms; falling back to polling scan", WINEVENT_WAIT_TIMEOUT_MS);
+
             // Wait for ANY window to appear
             let max_attempts = 20;
             debug!("Waiting for application window to appear (max {} attempts)", max_attempts);
-            
+
             for attempt in 1..=max_attempts {
                 // Progressive wait times
                 let wait_ms = if attempt < 5 {
@@ -363,7 +1053,21 @@ pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
                 
                 trace!("Attempt {}/{}: waiting {}ms", attempt, max_attempts, wait_ms);
                 thread::sleep(Duration::from_millis(wait_ms));
-                
+
+                // Match the spawned process's own window (or a child process's, in case it
+                // launched via a short-lived stub) deterministically by PID, before falling
+                // back to guessing from the title.
+                if let Some(hwnd) = find_main_window_for_pid(pid) {
+                    info!("Found window for spawned PID {} by PID match (attempt {})", pid, attempt);
+                    let result = activate_window(hwnd);
+                    if result {
+                        info!("Successfully activated window for PID {}", pid);
+                    } else {
+                        error!("Failed to activate window for PID {}", pid);
+                    }
+                    return LaunchOutcome { success: result, pid: Some(pid) };
+                }
+
                 // Get updated window list
                 let new_windows = scan_for_all_windows();
                 trace!("Found {} windows after waiting", new_windows.len());
@@ -383,7 +1087,7 @@ pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
                             } else {
                                 error!("Failed to activate new window: '{}'", window_title);
                             }
-                            return result;
+                            return LaunchOutcome { success: result, pid: Some(pid) };
                         }
                     }
                     
@@ -397,7 +1101,7 @@ pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
                         } else {
                             error!("Failed to activate new window: '{}'", window_title);
                         }
-                        return result;
+                        return LaunchOutcome { success: result, pid: Some(pid) };
                     }
                 }
                 
@@ -411,7 +1115,7 @@ pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
                     } else {
                         error!("Failed to activate new window: '{}'", found_window);
                     }
-                    return result;
+                    return LaunchOutcome { success: result, pid: Some(pid) };
                 }
                 
                 if attempt == 5 {
@@ -423,13 +1127,16 @@ pub fn launch_or_activate_application(app_path: &str, xpath: &str) -> bool {
                 }
             }
             
-            // If we still can't find it, assume success anyway
-            warn!("Could not find window after {} attempts, assuming success", max_attempts);
-            true
+            // Neither the WinEvent hook nor the polling scan ever saw a matching window,
+            // so report the genuine failure instead of assuming the launch worked anyway.
+            error!("Could not find window after {} attempts", max_attempts);
+            LaunchOutcome { success: false, pid: Some(pid) }
         },
         Err(e) => {
+            uninstall_winevent_hooks(winevent_hooks);
+            clear_winevent_state();
             error!("Failed to spawn application process: {} - Error: {:?}", app_path, e);
-            false
+            LaunchOutcome { success: false, pid: None }
         }
     }
 }
\ No newline at end of file
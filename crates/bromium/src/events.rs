@@ -0,0 +1,95 @@
+//! Event-driven UI tree invalidation: instead of guessing when to re-scan the whole
+//! desktop, register UIA StructureChanged and AutomationPropertyChanged (Name/Value/
+//! IsEnabled) handlers once per process and let them flip `tree_needs_update` /
+//! notify a condvar, following the single long-lived automation client pattern
+//! accesskit_windows uses for its own Windows event subscriptions.
+use std::sync::{Condvar, Mutex, OnceLock};
+
+use log::{error, info};
+use uiautomation::types::{TreeScope, UIProperty};
+use uiautomation::variants::Variant;
+use uiautomation::{UIAutomation, UIElement};
+
+use crate::windriver::WINDRIVER;
+
+/// Set to `true` whenever a watched UIA event fires; `WinDriver::wait_for_element` and
+/// `WinDriver::wait_until_stable` reset it to `false` after each wait on
+/// `TREE_DIRTY_CONDVAR`.
+pub static TREE_DIRTY: Mutex<bool> = Mutex::new(false);
+pub static TREE_DIRTY_CONDVAR: Condvar = Condvar::new();
+
+/// Keeps the `UIAutomation` client (and therefore its COM event subscriptions) alive
+/// for the life of the process; the handlers are registered once, the first time a
+/// `WinDriver` is created.
+static EVENT_UIA: OnceLock<UIAutomation> = OnceLock::new();
+
+struct StructureChangedHandler;
+
+impl uiautomation::events::CustomStructureChangedEventHandler for StructureChangedHandler {
+    fn handle(&self, _sender: &UIElement, _change_type: uiautomation::types::StructureChangeType, _runtime_id: Option<Vec<i32>>) -> uiautomation::Result<()> {
+        mark_dirty();
+        Ok(())
+    }
+}
+
+struct PropertyChangedHandler;
+
+impl uiautomation::events::CustomPropertyChangedEventHandler for PropertyChangedHandler {
+    fn handle(&self, _sender: &UIElement, _property: UIProperty, _value: Variant) -> uiautomation::Result<()> {
+        mark_dirty();
+        Ok(())
+    }
+}
+
+/// Flip the dirty flag, wake anyone blocked on it, and mark the global driver's
+/// `tree_needs_update` so other callers can see a change happened even without waiting.
+fn mark_dirty() {
+    if let Ok(mut dirty) = TREE_DIRTY.lock() {
+        *dirty = true;
+    }
+    TREE_DIRTY_CONDVAR.notify_all();
+
+    if let Ok(mut guard) = WINDRIVER.lock() {
+        if let Some(driver) = guard.as_mut() {
+            driver.tree_needs_update = true;
+        }
+    }
+}
+
+/// Register the structure-changed and property-changed handlers on the desktop root,
+/// once per process. Safe to call more than once: once a call has stored the automation
+/// client, later calls return immediately; a call that fails to create the client or to
+/// get the root element stores nothing, so the next call tries again.
+pub fn ensure_registered() {
+    if EVENT_UIA.get().is_some() {
+        return;
+    }
+
+    let uia = match UIAutomation::new() {
+        Ok(uia) => uia,
+        Err(e) => {
+            error!("Failed to create UIAutomation instance for event handlers: {:?}", e);
+            return;
+        }
+    };
+
+    let root = match uia.get_root_element() {
+        Ok(root) => root,
+        Err(e) => {
+            error!("Failed to get root element for event handlers: {:?}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = uia.add_structure_changed_event_handler(&root, TreeScope::Subtree, None, &StructureChangedHandler) {
+        error!("Failed to register structure changed event handler: {:?}", e);
+    }
+
+    let watched_properties = [UIProperty::Name as i32, UIProperty::ValueValue as i32, UIProperty::IsEnabled as i32];
+    if let Err(e) = uia.add_property_changed_event_handler(&root, TreeScope::Subtree, None, &PropertyChangedHandler, &watched_properties) {
+        error!("Failed to register property changed event handler: {:?}", e);
+    }
+
+    info!("Registered UIA structure/property changed event handlers for tree invalidation");
+    let _ = EVENT_UIA.set(uia);
+}
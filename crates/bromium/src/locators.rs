@@ -0,0 +1,135 @@
+//! Locator strategies beyond exact XPath or screen coordinates: lookup by automation id,
+//! control type, or name (substring/regex), plus a best-match fuzzy mode inspired by
+//! pywinauto's `findbestmatch` - when no exact name matches, every candidate is scored
+//! against the requested text and the highest scorer above a threshold wins.
+use log::{debug, warn};
+use pyo3::prelude::*;
+use regex::Regex;
+use uitree::UITreeXML;
+
+use crate::windriver::Element;
+
+fn element_from_index(ui_tree: &UITreeXML, index: usize) -> Element {
+    let (_, props) = ui_tree.node(index);
+    let xpath = ui_tree.get_xpath_for_element(index, true);
+    let bounding_rect = props.get_bounding_rectangle();
+    Element::new(
+        props.get_name().clone(),
+        xpath,
+        props.get_handle(),
+        props.get_runtime_id().clone(),
+        (bounding_rect.get_left(), bounding_rect.get_top(), bounding_rect.get_right(), bounding_rect.get_bottom()),
+    )
+}
+
+/// Every element whose automation id matches exactly.
+pub fn find_by_automation_id(ui_tree: &UITreeXML, automation_id: &str) -> Vec<Element> {
+    ui_tree.get_elements().iter()
+        .filter(|e| e.get_element_props().get_automation_id() == automation_id)
+        .map(|e| element_from_index(ui_tree, e.get_tree_index()))
+        .collect()
+}
+
+/// Every element whose control type matches exactly (case-insensitive).
+pub fn find_by_control_type(ui_tree: &UITreeXML, control_type: &str) -> Vec<Element> {
+    ui_tree.get_elements().iter()
+        .filter(|e| e.get_element_props().get_control_type().eq_ignore_ascii_case(control_type))
+        .map(|e| element_from_index(ui_tree, e.get_tree_index()))
+        .collect()
+}
+
+/// Every element whose name contains `pattern` (case-insensitive substring), or matches it
+/// as a regex when `use_regex` is set.
+pub fn find_by_name(ui_tree: &UITreeXML, pattern: &str, use_regex: bool) -> PyResult<Vec<Element>> {
+    let matches: Vec<usize> = if use_regex {
+        let re = Regex::new(pattern).map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid regex: {e}")))?;
+        ui_tree.filter_by_regex(&re)
+    } else {
+        ui_tree.filter_by_query(pattern)
+    };
+
+    Ok(matches.into_iter().map(|index| element_from_index(ui_tree, index)).collect())
+}
+
+fn normalize(text: &str) -> String {
+    text.to_lowercase().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Classic Levenshtein edit distance between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (la, lb) = (a.len(), b.len());
+
+    let mut dp = vec![vec![0usize; lb + 1]; la + 1];
+    for (i, row) in dp.iter_mut().enumerate() { row[0] = i; }
+    for j in 0..=lb { dp[0][j] = j; }
+
+    for i in 1..=la {
+        for j in 1..=lb {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1).min(dp[i][j - 1] + 1).min(dp[i - 1][j - 1] + cost);
+        }
+    }
+
+    dp[la][lb]
+}
+
+/// `1.0` for identical strings, `0.0` for maximally different ones.
+fn similarity(a: &str, b: &str) -> f64 {
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - (levenshtein(a, b) as f64 / max_len as f64)
+}
+
+struct ScoredCandidate {
+    element: Element,
+    score: f64,
+}
+
+/// How close two candidates' scores need to be to count as tied for the top spot.
+const AMBIGUITY_MARGIN: f64 = 0.05;
+
+/// Rank every element's name against `text` and return the highest scorer, provided it
+/// clears `threshold`. If several top candidates are too close to call, an error listing
+/// them is returned instead of guessing.
+pub fn find_best_match(ui_tree: &UITreeXML, text: &str, threshold: f64) -> PyResult<Element> {
+    debug!("locators::find_best_match called for text: '{}'", text);
+    let target = normalize(text);
+
+    let mut scored: Vec<ScoredCandidate> = ui_tree.get_elements().iter()
+        .map(|e| {
+            let name = normalize(e.get_element_props().get_name());
+            let score = similarity(&target, &name);
+            ScoredCandidate { element: element_from_index(ui_tree, e.get_tree_index()), score }
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+    let best_score = scored.first()
+        .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("No elements to match against"))?
+        .score;
+
+    if best_score < threshold {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "No element matched '{}' above threshold {:.2} (best score: {:.2})", text, threshold, best_score
+        )));
+    }
+
+    let contenders: Vec<&ScoredCandidate> = scored.iter()
+        .take_while(|c| best_score - c.score <= AMBIGUITY_MARGIN)
+        .collect();
+
+    if contenders.len() > 1 {
+        warn!("Ambiguous best match for '{}': {} candidates within {:.2} of the top score", text, contenders.len(), AMBIGUITY_MARGIN);
+        let names: Vec<String> = contenders.iter().map(|c| format!("'{}' ({:.2})", c.element.get_name(), c.score)).collect();
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Ambiguous match for '{}': {}", text, names.join(", ")
+        )));
+    }
+
+    Ok(scored.into_iter().next().unwrap().element)
+}
@@ -1,27 +1,15 @@
-use log::{Level, LevelFilter, Metadata, Record};
 use pyo3::prelude::*;
-use std::sync::Mutex;
+use std::sync::OnceLock;
 
-static LOGGER: BromiumLogger = BromiumLogger;
-static LOG_LEVEL: Mutex<LevelFilter> = Mutex::new(LevelFilter::Debug);
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::reload;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
 
-struct BromiumLogger;
-
-impl log::Log for BromiumLogger {
-    fn enabled(&self, metadata: &Metadata) -> bool {
-        let level = LOG_LEVEL.lock().unwrap();
-        metadata.level() <= *level
-    }
-
-    fn log(&self, record: &Record) {
-        if self.enabled(record.metadata()) {
-            let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
-            println!("{}: - bromium - {} - {}", timestamp, record.level(), record.args());
-        }
-    }
-
-    fn flush(&self) {}
-}
+/// The reload handle for the `EnvFilter` layer, stashed away so `set_log_level`/
+/// `get_log_level` can change verbosity at runtime instead of baking it in at
+/// `init_logger` time the way the old `Mutex<LevelFilter>` + `log::set_max_level` pair did.
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, tracing_subscriber::Registry>> = OnceLock::new();
 
 #[pyclass]
 #[derive(Debug, Clone, Copy)]
@@ -33,42 +21,65 @@ pub enum LogLevel {
     Trace,
 }
 
-impl From<LogLevel> for LevelFilter {
-    fn from(level: LogLevel) -> Self {
-        match level {
-            LogLevel::Error => LevelFilter::Error,
-            LogLevel::Warn => LevelFilter::Warn,
-            LogLevel::Info => LevelFilter::Info,
-            LogLevel::Debug => LevelFilter::Debug,
-            LogLevel::Trace => LevelFilter::Trace,
+impl LogLevel {
+    fn as_filter_str(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
         }
     }
 }
 
+/// Initialize the `tracing` subscriber with the human-readable formatting layer.
 pub fn init_logger() {
+    init_logger_with(false)
+}
+
+/// Initialize the `tracing` subscriber: an `EnvFilter` layer (behind a reload handle so
+/// `set_log_level` can swap it at runtime) plus a formatting layer, optionally JSON instead
+/// of human-readable when `json` is set - for feeding logs to machine ingestion rather than
+/// a terminal.
+pub fn init_logger_with(json: bool) {
     static INIT: std::sync::Once = std::sync::Once::new();
     INIT.call_once(|| {
-        log::set_logger(&LOGGER)
-            .map(|()| log::set_max_level(LevelFilter::Trace))
+        let (filter, handle) = reload::Layer::new(EnvFilter::new(LogLevel::Debug.as_filter_str()));
+        let _ = FILTER_HANDLE.set(handle);
+
+        let fmt_layer: Box<dyn Layer<tracing_subscriber::Registry> + Send + Sync> = if json {
+            Box::new(tracing_subscriber::fmt::layer().json())
+        } else {
+            Box::new(tracing_subscriber::fmt::layer())
+        };
+
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(fmt_layer)
+            .try_init()
             .expect("Failed to initialize logger");
     });
 }
 
-pub fn set_log_level_internal(level: LevelFilter) {
-    let mut log_level = LOG_LEVEL.lock().unwrap();
-    *log_level = level;
-    log::set_max_level(level);
+pub fn set_log_level_internal(level: LogLevel) {
+    if let Some(handle) = FILTER_HANDLE.get() {
+        let _ = handle.modify(|filter| *filter = EnvFilter::new(level.as_filter_str()));
+    }
 }
 
 #[pyfunction]
 pub fn set_log_level(level: LogLevel) -> PyResult<()> {
-    set_log_level_internal(level.into());
-    log::info!("Log level set to: {:?}", level);
+    set_log_level_internal(level);
+    tracing::info!(?level, "Log level set");
     Ok(())
 }
 
 #[pyfunction]
 pub fn get_log_level() -> PyResult<String> {
-    let level = LOG_LEVEL.lock().unwrap();
-    Ok(format!("{:?}", *level))
-}
\ No newline at end of file
+    let level = FILTER_HANDLE
+        .get()
+        .map(|handle| handle.with_current(|filter| filter.to_string()).unwrap_or_default())
+        .unwrap_or_default();
+    Ok(level)
+}
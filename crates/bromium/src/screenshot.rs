@@ -0,0 +1,191 @@
+//! General-purpose screen/window/element image capture, generalizing the old
+//! single-purpose `WinDriver::take_screenshot` into a capture surface useful for
+//! debugging failed element lookups and for visual-diff testing: capture one monitor,
+//! every monitor, or crop down to just one element's bounding rectangle - with a choice
+//! of output path, image format, and (for JPEG) a quality value, echoing the
+//! configurable `ScreenshotOptions` in geckodriver's marionette layer.
+use std::fs;
+use std::path::PathBuf;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageFormat, RgbaImage};
+use log::{debug, error, info};
+use pyo3::prelude::*;
+use screen_capture::Monitor;
+
+use crate::windriver::Element;
+
+/// Output image format for a capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureFormat {
+    Png,
+    Jpeg,
+}
+
+impl CaptureFormat {
+    fn parse(format: &str) -> PyResult<Self> {
+        match format.to_lowercase().as_str() {
+            "png" => Ok(CaptureFormat::Png),
+            "jpeg" | "jpg" => Ok(CaptureFormat::Jpeg),
+            other => Err(pyo3::exceptions::PyValueError::new_err(format!("Unsupported image format: {other}"))),
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            CaptureFormat::Png => "png",
+            CaptureFormat::Jpeg => "jpg",
+        }
+    }
+}
+
+fn default_output_dir() -> PathBuf {
+    std::env::temp_dir().join("bromium_screenshots")
+}
+
+fn normalized(name: &str) -> String {
+    name.replace(['|', '\\', ':', '/'], "")
+}
+
+/// Encode `image` as `format` (honoring `quality` for JPEG), write it to `output_path`,
+/// and return the encoded bytes.
+fn encode_and_save(image: &RgbaImage, output_path: &std::path::Path, format: CaptureFormat, quality: u8) -> PyResult<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::new();
+
+    match format {
+        CaptureFormat::Png => {
+            image.write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Png).map_err(|e| {
+                error!("Failed to encode screenshot as PNG: {:?}", e);
+                pyo3::exceptions::PyValueError::new_err("Failed to encode screenshot as PNG")
+            })?;
+        }
+        CaptureFormat::Jpeg => {
+            let rgb = DynamicImage::ImageRgba8(image.clone()).to_rgb8();
+            JpegEncoder::new_with_quality(&mut bytes, quality).encode_image(&rgb).map_err(|e| {
+                error!("Failed to encode screenshot as JPEG: {:?}", e);
+                pyo3::exceptions::PyValueError::new_err("Failed to encode screenshot as JPEG")
+            })?;
+        }
+    }
+
+    if let Some(parent) = output_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+
+    fs::write(output_path, &bytes).map_err(|e| {
+        error!("Failed to write screenshot to {:?}: {:?}", output_path, e);
+        pyo3::exceptions::PyValueError::new_err("Failed to save screenshot")
+    })?;
+
+    Ok(bytes)
+}
+
+/// Capture the monitor at `index` (as returned by `Monitor::all()`), saving it to
+/// `output_path` (or a generated path under the temp screenshot directory) and returning
+/// `(saved_path, encoded_bytes)`.
+pub fn capture_monitor(index: usize, output_path: Option<String>, format: &str, quality: u8) -> PyResult<(String, Vec<u8>)> {
+    debug!("screenshot::capture_monitor called for index {}", index);
+    let format = CaptureFormat::parse(format)?;
+
+    let monitors = Monitor::all().map_err(|e| {
+        error!("Failed to enumerate monitors: {:?}", e);
+        pyo3::exceptions::PyValueError::new_err("Failed to enumerate monitors")
+    })?;
+
+    let monitor = monitors.get(index).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!("No monitor at index {index}"))
+    })?;
+
+    let image = monitor.capture_image().map_err(|e| {
+        error!("Failed to capture monitor {}: {:?}", index, e);
+        pyo3::exceptions::PyValueError::new_err("Failed to capture monitor")
+    })?;
+
+    let path = output_path.map(PathBuf::from).unwrap_or_else(|| {
+        default_output_dir().join(format!(
+            "monitor-{}.{}",
+            normalized(&monitor.name().unwrap_or_default()),
+            format.extension()
+        ))
+    });
+
+    let bytes = encode_and_save(&image, &path, format, quality)?;
+    info!("Captured monitor {} to {:?}", index, path);
+    Ok((path.to_string_lossy().to_string(), bytes))
+}
+
+/// Capture every connected monitor, returning the saved path for each.
+pub fn capture_all_monitors(output_dir: Option<String>, format: &str, quality: u8) -> PyResult<Vec<String>> {
+    debug!("screenshot::capture_all_monitors called");
+    let format = CaptureFormat::parse(format)?;
+
+    let monitors = Monitor::all().map_err(|e| {
+        error!("Failed to enumerate monitors: {:?}", e);
+        pyo3::exceptions::PyValueError::new_err("Failed to enumerate monitors")
+    })?;
+
+    let out_dir = output_dir.map(PathBuf::from).unwrap_or_else(default_output_dir);
+
+    let mut paths = Vec::with_capacity(monitors.len());
+    for monitor in &monitors {
+        let image = monitor.capture_image().map_err(|e| {
+            error!("Failed to capture monitor: {:?}", e);
+            pyo3::exceptions::PyValueError::new_err("Failed to capture monitor")
+        })?;
+        let path = out_dir.join(format!(
+            "monitor-{}.{}",
+            normalized(&monitor.name().unwrap_or_default()),
+            format.extension()
+        ));
+        encode_and_save(&image, &path, format, quality)?;
+        paths.push(path.to_string_lossy().to_string());
+    }
+
+    info!("Captured {} monitor(s)", paths.len());
+    Ok(paths)
+}
+
+/// Capture just `element`'s bounding rectangle, cropped from a capture of the monitor it
+/// resides on.
+pub fn capture_element(element: &Element, output_path: Option<String>, format: &str, quality: u8) -> PyResult<(String, Vec<u8>)> {
+    debug!("screenshot::capture_element called for element: {}", element.get_name());
+    let format = CaptureFormat::parse(format)?;
+
+    let (left, top, right, bottom) = element.get_bounding_rectangle();
+
+    let monitors = Monitor::all().map_err(|e| {
+        error!("Failed to enumerate monitors: {:?}", e);
+        pyo3::exceptions::PyValueError::new_err("Failed to enumerate monitors")
+    })?;
+
+    let monitor = monitors.iter().find(|m| {
+        let (mx, my) = (m.x().unwrap_or(0), m.y().unwrap_or(0));
+        let (mw, mh) = (m.width().unwrap_or(0) as i32, m.height().unwrap_or(0) as i32);
+        left >= mx && top >= my && left < mx + mw && top < my + mh
+    }).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err("Could not find a monitor containing this element")
+    })?;
+
+    let monitor_image = monitor.capture_image().map_err(|e| {
+        error!("Failed to capture monitor for element {}: {:?}", element.get_name(), e);
+        pyo3::exceptions::PyValueError::new_err("Failed to capture monitor")
+    })?;
+
+    let (mx, my) = (monitor.x().unwrap_or(0), monitor.y().unwrap_or(0));
+    let crop_x = (left - mx).max(0) as u32;
+    let crop_y = (top - my).max(0) as u32;
+    let crop_w = (right - left).max(0) as u32;
+    let crop_h = (bottom - top).max(0) as u32;
+
+    let cropped = image::imageops::crop_imm(&monitor_image, crop_x, crop_y, crop_w, crop_h).to_image();
+
+    let path = output_path.map(PathBuf::from).unwrap_or_else(|| {
+        default_output_dir().join(format!("element-{}.{}", normalized(&element.get_name()), format.extension()))
+    });
+
+    let bytes = encode_and_save(&cropped, &path, format, quality)?;
+    info!("Captured element '{}' to {:?}", element.get_name(), path);
+    Ok((path.to_string_lossy().to_string(), bytes))
+}
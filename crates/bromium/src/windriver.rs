@@ -1,16 +1,26 @@
 use std::thread;
 use std::sync::Mutex;
 use std::sync::mpsc::{channel, Receiver, Sender};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use pyo3::prelude::*;
 // use uiautomation::types::Handle;
 
+use serde::{Deserialize, Serialize};
+
 use crate::sreen_context::ScreenContext;
 use crate::uiauto::{get_ui_element_by_runtimeid}; // get_ui_element_by_xpath, get_element_by_xpath
 use uitree::{UITreeXML, get_all_elements_xml};
 // use crate::uiexplore::UITree;
-use crate::app_control::launch_or_activate_application;
+use crate::app_control::{
+    launch_or_activate_application, enumerate_child_windows as enumerate_child_windows_impl,
+    get_window_info as get_window_info_impl, activate_or_launch_with_options,
+    ActivationOptions, WindowMatchMode,
+};
+use std::collections::HashMap;
+use crate::screenshot;
+use crate::locators;
+use crate::events;
 
 #[allow(unused_imports)]
 use crate::commons::execute_with_timeout;
@@ -19,15 +29,168 @@ use screen_capture::{Window, Monitor};
 
 use fs_extra::dir;
 
-use windows::Win32::Foundation::{POINT, RECT}; //HWND, 
-use windows::Win32::UI::WindowsAndMessaging::{GetCursorPos}; //WindowFromPoint
+use windows::Win32::Foundation::{CloseHandle, HWND, POINT, RECT, WAIT_OBJECT_0, WAIT_TIMEOUT};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetCursorPos, GetSystemMetrics, GetWindowRect, IsIconic, MoveWindow, SetCursorPos, SetForegroundWindow, SetWindowPos, ShowWindow,
+    mouse_event, HWND_NOTOPMOST, HWND_TOPMOST, MOUSEEVENTF_ABSOLUTE, MOUSEEVENTF_HWHEEL, MOUSEEVENTF_LEFTDOWN,
+    MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MOVE, MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEEVENTF_WHEEL,
+    SM_CXSCREEN, SM_CYSCREEN, SWP_NOMOVE, SWP_NOSIZE, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE,
+}; //WindowFromPoint
+use windows::Win32::System::Threading::{
+    GetExitCodeProcess, OpenProcess, TerminateProcess, WaitForSingleObject, INFINITE,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SYNCHRONIZE, PROCESS_TERMINATE, STILL_ACTIVE,
+};
+use windows::Win32::Graphics::Gdi::{
+    BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC, GetDIBits,
+    ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, SRCCOPY,
+};
+use image::RgbaImage;
+
+/// The Win32 `WHEEL_DELTA` constant: one wheel "notch" in `mouse_event`'s wheel data.
+const WHEEL_DELTA: i32 = 120;
+/// Number of interpolated cursor positions a drag moves through between source and
+/// destination, so the target application sees a drag rather than a teleport.
+const DRAG_STEPS: i32 = 20;
+const DRAG_STEP_DELAY_MS: u64 = 10;
 
 use uiautomation::{UIElement}; //UIAutomation, 
 
 use log::{debug, error, info, trace, warn};
 
-static WINDRIVER: Mutex<Option<WinDriver>> = Mutex::new(None);
+pub(crate) static WINDRIVER: Mutex<Option<WinDriver>> = Mutex::new(None);
+
+/// One user-input action captured while recording, modeled on the action kinds the
+/// pywinauto-recorder player replays.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum RecordedAction {
+    LeftClick,
+    RightClick,
+    DoubleClick,
+    HoldClick { holdkeys: String },
+    SendKeys { keys: String },
+    SendText { text: String },
+    Wheel { notches: i32, horizontal: bool },
+    Move,
+}
+
+/// A single step of a recorded script: the target element (resolved the same way
+/// `convert_to_ui_element` resolves any other stale element - by runtime id, falling back
+/// to xpath), the action it performed, and how long to wait before replaying it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedStep {
+    xpath: String,
+    runtime_id: Vec<i32>,
+    action: RecordedAction,
+    delay_ms: u64,
+}
+
+/// If recording is active on the global driver, append a step for `action` against
+/// `element`, timed relative to the previous recorded step (or to when recording started,
+/// for the first step).
+fn record_step(element: &Element, action: RecordedAction) {
+    let mut driver_guard = match WINDRIVER.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            error!("WINDRIVER lock is poisoned while recording, recovering...");
+            poisoned.into_inner()
+        }
+    };
+
+    if let Some(driver) = driver_guard.as_mut() {
+        if !driver.recording {
+            return;
+        }
+
+        let now = Instant::now();
+        let delay_ms = driver.last_record_instant
+            .map(|prev| now.duration_since(prev).as_millis() as u64)
+            .unwrap_or(0);
+        driver.last_record_instant = Some(now);
+
+        driver.recorded_steps.push(RecordedStep {
+            xpath: element.get_xpath(),
+            runtime_id: element.get_runtime_id(),
+            action,
+            delay_ms,
+        });
+    }
+}
+
+
+/// The outcome of `WinDriver::launch_or_activate_app`: whether an existing window was
+/// activated or a new process spawned, and the PID of that process when one was spawned
+/// (an activated pre-existing window never has one), so later calls can target it with
+/// `WinDriver::is_app_running`, `wait_for_exit`, or `terminate_app`.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct LaunchResult {
+    success: bool,
+    pid: Option<u32>,
+}
+
+#[pymethods]
+impl LaunchResult {
+    pub fn __repr__(&self) -> PyResult<String> {
+        PyResult::Ok(format!("<LaunchResult success={} pid={:?}>", self.success, self.pid))
+    }
+
+    pub fn get_success(&self) -> bool {
+        self.success
+    }
+
+    pub fn get_pid(&self) -> Option<u32> {
+        self.pid
+    }
+}
+
+/// A window's geometry and identity, as gathered by `WinDriver::get_window_info` - lets
+/// a caller compute a control's absolute screen location and feed it into
+/// `ScreenContext`, rather than assuming absolute screen coordinates.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct WindowInfo {
+    bounding_rectangle: (i32, i32, i32, i32),
+    client_rectangle: (i32, i32, i32, i32),
+    is_minimized: bool,
+    is_maximized: bool,
+    pid: u32,
+    class_name: String,
+}
+
+#[pymethods]
+impl WindowInfo {
+    pub fn __repr__(&self) -> PyResult<String> {
+        PyResult::Ok(format!(
+            "<WindowInfo class='{}' pid={} bounding_rectangle={:?} minimized={} maximized={}>",
+            self.class_name, self.pid, self.bounding_rectangle, self.is_minimized, self.is_maximized
+        ))
+    }
+
+    pub fn get_bounding_rectangle(&self) -> (i32, i32, i32, i32) {
+        self.bounding_rectangle
+    }
 
+    pub fn get_client_rectangle(&self) -> (i32, i32, i32, i32) {
+        self.client_rectangle
+    }
+
+    pub fn get_is_minimized(&self) -> bool {
+        self.is_minimized
+    }
+
+    pub fn get_is_maximized(&self) -> bool {
+        self.is_maximized
+    }
+
+    pub fn get_pid(&self) -> u32 {
+        self.pid
+    }
+
+    pub fn get_class_name(&self) -> String {
+        self.class_name.clone()
+    }
+}
 
 #[pyclass]
 #[derive(Debug, Clone)]
@@ -79,7 +242,11 @@ impl Element {
     pub fn get_runtime_id(&self) -> Vec<i32> {
         self.runtime_id.clone()
     }
-    
+
+    pub fn get_bounding_rectangle(&self) -> (i32, i32, i32, i32) {
+        (self.bounding_rectangle.left, self.bounding_rectangle.top, self.bounding_rectangle.right, self.bounding_rectangle.bottom)
+    }
+
     // Region mouse methods
     pub fn send_click(&self) -> PyResult<()> {
         debug!("Element::send_click called for element: {}", self.name);
@@ -87,6 +254,7 @@ impl Element {
             match e.click() {
                 Ok(_) => {
                     info!("Successfully clicked on element: {:#?}", e);
+                    record_step(self, RecordedAction::LeftClick);
                 }
                 Err(e) => {
                     error!("Error clicking on element: {:?}", e);
@@ -106,6 +274,7 @@ impl Element {
             match e.double_click() {
                 Ok(_) => {
                     info!("Double clicked on element: {:#?}", e);
+                    record_step(self, RecordedAction::DoubleClick);
                 }
                 Err(e) => {
                     error!("Error double clicking on element: {:?}", e);
@@ -124,6 +293,7 @@ impl Element {
             match e.right_click() {
                 Ok(_) => {
                     info!("Right clicked on element: {:#?}", e);
+                    record_step(self, RecordedAction::RightClick);
                 }
                 Err(e) => {
                     error!("Error right clicking on element: {:?}", e);
@@ -142,6 +312,7 @@ impl Element {
             match e.hold_click(&holdkeys) {
                 Ok(_) => {
                     info!("Hold clicked on element: {:#?}", e);
+                    record_step(self, RecordedAction::HoldClick { holdkeys: holdkeys.clone() });
                 }
                 Err(e) => {
                     error!("Error hold clicking on element: {:?}", e);
@@ -161,6 +332,7 @@ impl Element {
             match e.send_keys(&keys, 20) { // 20 ms interval for sending keys
                 Ok(_) => {
                     info!("Sent keys '{}' to element: {:#?}", keys, e);
+                    record_step(self, RecordedAction::SendKeys { keys: keys.clone() });
                 }
                 Err(e) => {
                     error!("Error sending keys to element: {:?}", e);
@@ -179,6 +351,7 @@ impl Element {
             match e.send_text(&text, 20) { // 20 ms interval for sending text
                 Ok(_) => {
                     info!("Sent text '{}' to element: {:#?}", text, e);
+                    record_step(self, RecordedAction::SendText { text: text.clone() });
                 }
                 Err(e) => {
                     error!("Error sending text to element: {:?}", e);
@@ -228,6 +401,128 @@ impl Element {
         PyResult::Ok(())
     }
 
+    // Region mouse movement, scroll and drag methods
+    /// Scroll the mouse wheel over this element's center.
+    ///
+    /// Args:
+    ///     notches (int): number of wheel notches to scroll; negative scrolls the
+    ///         opposite direction
+    ///     horizontal (bool): scroll horizontally instead of vertically
+    pub fn scroll(&self, notches: i32, horizontal: bool) -> PyResult<()> {
+        debug!("Element::scroll called with notches={} horizontal={} for element: {}", notches, horizontal, self.name);
+        let (x, y) = element_center(self);
+        unsafe {
+            if let Err(e) = SetCursorPos(x, y) {
+                error!("Error moving cursor before scroll: {:?}", e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move cursor for scroll"));
+            }
+            let flag = if horizontal { MOUSEEVENTF_HWHEEL } else { MOUSEEVENTF_WHEEL };
+            mouse_event(flag, 0, 0, (notches * WHEEL_DELTA) as u32, 0);
+        }
+        info!("Scrolled {} notch(es) ({}) over element: {}", notches, if horizontal { "horizontal" } else { "vertical" }, self.name);
+        record_step(self, RecordedAction::Wheel { notches, horizontal });
+        PyResult::Ok(())
+    }
+
+    /// Move the cursor to this element's bounding-rectangle center, without clicking.
+    pub fn move_to(&self) -> PyResult<()> {
+        debug!("Element::move_to called for element: {}", self.name);
+        let (x, y) = element_center(self);
+        unsafe {
+            if let Err(e) = SetCursorPos(x, y) {
+                error!("Error moving cursor to element: {:?}", e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move cursor"));
+            }
+        }
+        info!("Moved cursor to element: {} at ({}, {})", self.name, x, y);
+        record_step(self, RecordedAction::Move);
+        PyResult::Ok(())
+    }
+
+    /// Drag from this element's center to `target`'s center.
+    pub fn drag_to(&self, target: &Element) -> PyResult<()> {
+        debug!("Element::drag_to called for element: {} to element: {}", self.name, target.name);
+        let (start_x, start_y) = element_center(self);
+        let (end_x, end_y) = element_center(target);
+        drag(start_x, start_y, end_x, end_y)
+    }
+
+    /// Drag from this element's center to the absolute screen point `(x, y)`.
+    pub fn drag_to_point(&self, x: i32, y: i32) -> PyResult<()> {
+        debug!("Element::drag_to_point called for element: {} to ({}, {})", self.name, x, y);
+        let (start_x, start_y) = element_center(self);
+        drag(start_x, start_y, x, y)
+    }
+
+    // Region window management methods, operating on this element's top-level window
+    /// The current screen rectangle of this element's window, as `(left, top, right, bottom)`.
+    pub fn get_window_rect(&self) -> PyResult<(i32, i32, i32, i32)> {
+        debug!("Element::get_window_rect called for element: {}", self.name);
+        let mut rect = RECT::default();
+        unsafe {
+            if let Err(e) = GetWindowRect(window_handle(self), &mut rect) {
+                error!("Error getting window rect for element: {:?}", e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to get window rect"));
+            }
+        }
+        PyResult::Ok((rect.left, rect.top, rect.right, rect.bottom))
+    }
+
+    /// Move and/or resize this element's window.
+    pub fn move_window(&self, x: i32, y: i32, width: i32, height: i32) -> PyResult<()> {
+        debug!("Element::move_window called for element: {} to ({}, {}, {}, {})", self.name, x, y, width, height);
+        unsafe {
+            if let Err(e) = MoveWindow(window_handle(self), x, y, width, height, true) {
+                error!("Error moving window for element: {:?}", e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move window"));
+            }
+        }
+        PyResult::Ok(())
+    }
+
+    /// Minimize this element's window.
+    pub fn minimize(&self) -> PyResult<()> {
+        debug!("Element::minimize called for element: {}", self.name);
+        unsafe {
+            let _ = ShowWindow(window_handle(self), SW_MINIMIZE);
+        }
+        PyResult::Ok(())
+    }
+
+    /// Maximize this element's window.
+    pub fn maximize(&self) -> PyResult<()> {
+        debug!("Element::maximize called for element: {}", self.name);
+        unsafe {
+            let _ = ShowWindow(window_handle(self), SW_MAXIMIZE);
+        }
+        PyResult::Ok(())
+    }
+
+    /// Restore this element's window if it is currently minimized.
+    pub fn restore(&self) -> PyResult<()> {
+        debug!("Element::restore called for element: {}", self.name);
+        unsafe {
+            let handle = window_handle(self);
+            if IsIconic(handle).as_bool() {
+                let _ = ShowWindow(handle, SW_RESTORE);
+            }
+        }
+        PyResult::Ok(())
+    }
+
+    /// Pin or unpin this element's window as always-on-top.
+    pub fn set_topmost(&self, topmost: bool) -> PyResult<()> {
+        debug!("Element::set_topmost called for element: {} topmost={}", self.name, topmost);
+        let insert_after = if topmost { HWND_TOPMOST } else { HWND_NOTOPMOST };
+        unsafe {
+            if let Err(e) = SetWindowPos(window_handle(self), Some(insert_after), 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE) {
+                error!("Error setting topmost for element: {:?}", e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to set window topmost state"));
+            }
+        }
+        PyResult::Ok(())
+    }
+
 }
 
 impl Default for Element {
@@ -247,6 +542,173 @@ impl Default for Element {
     }
 }
 
+/// The center point of `element`'s bounding rectangle, in screen pixels.
+fn element_center(element: &Element) -> (i32, i32) {
+    let rect = element.bounding_rectangle;
+    ((rect.left + rect.right) / 2, (rect.top + rect.bottom) / 2)
+}
+
+/// Convert a screen point to the normalized `0..65535` coordinate space `mouse_event`
+/// expects alongside `MOUSEEVENTF_ABSOLUTE`, scaled by the primary screen's metrics.
+fn to_absolute(x: i32, y: i32) -> (i32, i32) {
+    unsafe {
+        let screen_w = GetSystemMetrics(SM_CXSCREEN).max(1);
+        let screen_h = GetSystemMetrics(SM_CYSCREEN).max(1);
+        ((x * 65535) / screen_w, (y * 65535) / screen_h)
+    }
+}
+
+/// Move the cursor to `(x, y)` via an absolute `mouse_event`, then emit `down_flag`
+/// followed by `up_flag` - the synthesis pywinauto-recorder's player uses so replay
+/// doesn't depend on `SetCursorPos` leaving the cursor where a click handler expects it.
+fn synthesize_click(x: i32, y: i32, down_flag: windows::Win32::UI::WindowsAndMessaging::MOUSE_EVENT_FLAGS, up_flag: windows::Win32::UI::WindowsAndMessaging::MOUSE_EVENT_FLAGS) {
+    let (ax, ay) = to_absolute(x, y);
+    unsafe {
+        mouse_event(MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_MOVE, ax, ay, 0, 0);
+        mouse_event(down_flag, 0, 0, 0, 0);
+        mouse_event(up_flag, 0, 0, 0, 0);
+    }
+}
+
+/// `element`'s stored handle as an `HWND`, for the window-management Win32 calls.
+fn window_handle(element: &Element) -> HWND {
+    HWND(element.handle as *mut _)
+}
+
+/// Grab `hwnd`'s pixels via `BitBlt` into a compatible bitmap, cropping to `region`
+/// (screen coordinates) when given, or the whole window otherwise.
+fn capture_hwnd_region(hwnd: HWND, region: Option<RECT>) -> PyResult<RgbaImage> {
+    let mut window_rect = RECT::default();
+    unsafe {
+        GetWindowRect(hwnd, &mut window_rect).map_err(|e| {
+            error!("Failed to get window rect for screenshot: {:?}", e);
+            pyo3::exceptions::PyValueError::new_err("Failed to get window rect")
+        })?;
+    }
+
+    let (crop_x, crop_y, width, height) = match region {
+        Some(r) => (
+            (r.left - window_rect.left).max(0),
+            (r.top - window_rect.top).max(0),
+            (r.right - r.left).max(1),
+            (r.bottom - r.top).max(1),
+        ),
+        None => (0, 0, (window_rect.right - window_rect.left).max(1), (window_rect.bottom - window_rect.top).max(1)),
+    };
+
+    let mut buffer = vec![0u8; (width * height * 4) as usize];
+
+    unsafe {
+        let window_dc = GetDC(Some(hwnd));
+        if window_dc.is_invalid() {
+            error!("GetDC returned an invalid device context");
+            return Err(pyo3::exceptions::PyValueError::new_err("Failed to get window device context"));
+        }
+
+        let mem_dc = CreateCompatibleDC(Some(window_dc));
+        let bitmap = CreateCompatibleBitmap(window_dc, width, height);
+        let old_obj = SelectObject(mem_dc, bitmap.into());
+
+        let blt_result = BitBlt(mem_dc, 0, 0, width, height, Some(window_dc), crop_x, crop_y, SRCCOPY);
+
+        let mut bitmap_info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height, // negative: a top-down DIB, so rows come out already in screen order
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let dib_result = GetDIBits(mem_dc, bitmap, 0, height as u32, Some(buffer.as_mut_ptr() as *mut _), &mut bitmap_info, DIB_RGB_COLORS);
+
+        SelectObject(mem_dc, old_obj);
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(mem_dc);
+        ReleaseDC(Some(hwnd), window_dc);
+
+        if blt_result.is_err() || dib_result == 0 {
+            error!("BitBlt/GetDIBits failed while capturing window {:?}", hwnd);
+            return Err(pyo3::exceptions::PyValueError::new_err("Failed to capture window pixels"));
+        }
+    }
+
+    // GetDIBits returns BGRA; swap to RGBA in place for `image::RgbaImage`.
+    for pixel in buffer.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+        pixel[3] = 255;
+    }
+
+    RgbaImage::from_raw(width as u32, height as u32, buffer)
+        .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("Captured buffer did not match the expected image dimensions"))
+}
+
+/// Encode `image` as PNG and write it to `path` if given, falling back to a generated
+/// path (under the temp screenshot directory, named from `default_name` and sanitized
+/// through `normalized`) when it isn't. Returns the encoded bytes either way.
+fn encode_png(image: &RgbaImage, path: Option<String>, default_name: &str) -> PyResult<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::new();
+    image.write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png).map_err(|e| {
+        error!("Failed to encode screenshot as PNG: {:?}", e);
+        pyo3::exceptions::PyValueError::new_err("Failed to encode screenshot as PNG")
+    })?;
+
+    let path = path.unwrap_or_else(|| {
+        std::env::temp_dir()
+            .join("bromium_screenshots")
+            .join(format!("{}.png", normalized(default_name.to_string())))
+            .to_string_lossy()
+            .to_string()
+    });
+
+    if let Some(parent) = std::path::Path::new(&path).parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+    }
+
+    std::fs::write(&path, &bytes).map_err(|e| {
+        error!("Failed to write screenshot to {}: {:?}", path, e);
+        pyo3::exceptions::PyValueError::new_err("Failed to save screenshot")
+    })?;
+    info!("Saved screenshot to {}", path);
+
+    Ok(bytes)
+}
+
+/// Press the left mouse button at `(start_x, start_y)`, move the cursor through
+/// `DRAG_STEPS` interpolated positions to `(end_x, end_y)`, then release - so the target
+/// application sees a drag rather than a click-teleport-click.
+fn drag(start_x: i32, start_y: i32, end_x: i32, end_y: i32) -> PyResult<()> {
+    unsafe {
+        if let Err(e) = SetCursorPos(start_x, start_y) {
+            error!("Error moving cursor to drag start: {:?}", e);
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move cursor to drag start"));
+        }
+        mouse_event(MOUSEEVENTF_LEFTDOWN, 0, 0, 0, 0);
+
+        for step in 1..=DRAG_STEPS {
+            let t = step as f32 / DRAG_STEPS as f32;
+            let x = start_x + ((end_x - start_x) as f32 * t) as i32;
+            let y = start_y + ((end_y - start_y) as f32 * t) as i32;
+            if let Err(e) = SetCursorPos(x, y) {
+                error!("Error moving cursor during drag: {:?}", e);
+                mouse_event(MOUSEEVENTF_LEFTUP, 0, 0, 0, 0);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move cursor during drag"));
+            }
+            thread::sleep(Duration::from_millis(DRAG_STEP_DELAY_MS));
+        }
+
+        mouse_event(MOUSEEVENTF_LEFTUP, 0, 0, 0, 0);
+    }
+    info!("Dragged from ({}, {}) to ({}, {})", start_x, start_y, end_x, end_y);
+    PyResult::Ok(())
+}
+
 fn convert_to_ui_element(element: &Element) -> Result<UIElement, uiautomation::Error> {
     debug!("Element::convert_to_ui_element called.");
 
@@ -370,8 +832,15 @@ fn convert_to_ui_element(element: &Element) -> Result<UIElement, uiautomation::E
 pub struct WinDriver {
     timeout_ms: u64,
     ui_tree: UITreeXML,
-    tree_needs_update: bool,
+    pub(crate) tree_needs_update: bool,
     auto_refresh_on_stale: bool,
+    recording: bool,
+    recorded_steps: Vec<RecordedStep>,
+    last_record_instant: Option<Instant>,
+    /// PID of the process most recently spawned by `launch_or_activate_app`, if any. Only
+    /// the PID is kept (not an open HANDLE) since `WinDriver` is cloned into the global
+    /// singleton frequently; each process query opens and closes its own short-lived handle.
+    launched_pid: Option<u32>,
 }
 
 #[pymethods]
@@ -426,6 +895,10 @@ impl WinDriver {
             ui_tree,
             tree_needs_update: false,
             auto_refresh_on_stale: true, // Enable auto-refresh by default
+            recording: false,
+            recorded_steps: Vec::new(),
+            last_record_instant: None,
+            launched_pid: None,
         };
 
         // FIX BUG #5: Handle lock errors properly
@@ -440,6 +913,8 @@ impl WinDriver {
             }
         }
 
+        events::ensure_registered();
+
         info!("WinDriver successfully created with auto-refresh enabled (singleton instance)");
         Ok(driver)
     }
@@ -526,7 +1001,7 @@ impl WinDriver {
             &self.ui_tree
         };
 
-        if let Some(ui_element_in_tree) = crate::rectangle::get_point_bounding_rect(&cursor_position, ui_tree.get_elements()) {
+        if let Some((_, ui_element_in_tree)) = crate::rectangle::get_point_bounding_rect(&cursor_position, ui_tree.get_elements()) {
             let xpath = ui_tree.get_xpath_for_element(ui_element_in_tree.get_tree_index(), true);
             trace!("Found element with xpath: {}", xpath);
 
@@ -582,6 +1057,269 @@ impl WinDriver {
         PyResult::Ok(Element::new(name, xpath, handle, runtime_id, (bounding_rectangle.get_left(), bounding_rectangle.get_top(), bounding_rectangle.get_right(), bounding_rectangle.get_bottom())))
     }
 
+    /// Poll for an element matching `xpath` until it appears or `timeout_ms` elapses.
+    ///
+    /// Args:
+    ///     xpath (str): XPath identifying the element to wait for
+    ///     timeout_ms (int): Maximum time to wait, in milliseconds
+    ///     poll_interval_ms (int): Delay between re-checks, in milliseconds
+    ///
+    /// Returns:
+    ///     Element: the resolved element
+    pub fn wait_for_element_by_xpath(&self, xpath: String, timeout_ms: u64, poll_interval_ms: u64) -> PyResult<Element> {
+        debug!("WinDriver::wait_for_element_by_xpath called for xpath: {} (timeout={}ms)", xpath, timeout_ms);
+        poll_until(timeout_ms, poll_interval_ms, || {
+            self.get_ui_element_by_xpath(xpath.clone()).map_err(|_| format!("no element found for xpath '{}'", xpath))
+        })
+    }
+
+    /// Poll for an element at the given coordinates until one appears or `timeout_ms` elapses.
+    ///
+    /// Args:
+    ///     x (int): Screen x coordinate
+    ///     y (int): Screen y coordinate
+    ///     timeout_ms (int): Maximum time to wait, in milliseconds
+    ///     poll_interval_ms (int): Delay between re-checks, in milliseconds
+    ///
+    /// Returns:
+    ///     Element: the resolved element
+    pub fn wait_for_element_at(&self, x: i32, y: i32, timeout_ms: u64, poll_interval_ms: u64) -> PyResult<Element> {
+        debug!("WinDriver::wait_for_element_at called for coordinates: ({}, {}) (timeout={}ms)", x, y, timeout_ms);
+        poll_until(timeout_ms, poll_interval_ms, || {
+            self.get_ui_element(x, y).map_err(|_| format!("no element found at ({}, {})", x, y))
+        })
+    }
+
+    /// Poll the element at `xpath` until its text equals `expected` or `timeout_ms` elapses.
+    ///
+    /// Text is read from the element's value pattern when it has one, falling back to its
+    /// name otherwise.
+    ///
+    /// Args:
+    ///     xpath (str): XPath identifying the element to wait on
+    ///     expected (str): Text the element must have for the wait to succeed
+    ///     timeout_ms (int): Maximum time to wait, in milliseconds
+    ///     poll_interval_ms (int): Delay between re-checks, in milliseconds
+    ///
+    /// Returns:
+    ///     Element: the resolved element, once its text matches
+    pub fn wait_until_text(&self, xpath: String, expected: String, timeout_ms: u64, poll_interval_ms: u64) -> PyResult<Element> {
+        debug!("WinDriver::wait_until_text called for xpath: {} expecting '{}' (timeout={}ms)", xpath, expected, timeout_ms);
+        poll_until(timeout_ms, poll_interval_ms, || {
+            let element = self.get_ui_element_by_xpath(xpath.clone()).map_err(|_| "element not found".to_string())?;
+            let ui_element = convert_to_ui_element(&element).map_err(|e| format!("{:?}", e))?;
+            let text = current_text(&ui_element);
+            if text == expected {
+                Ok(element)
+            } else {
+                Err(text)
+            }
+        })
+    }
+
+    /// Poll the element at `xpath` until it reports enabled or `timeout_ms` elapses.
+    ///
+    /// Args:
+    ///     xpath (str): XPath identifying the element to wait on
+    ///     timeout_ms (int): Maximum time to wait, in milliseconds
+    ///     poll_interval_ms (int): Delay between re-checks, in milliseconds
+    ///
+    /// Returns:
+    ///     Element: the resolved element, once it is enabled
+    pub fn wait_until_enabled(&self, xpath: String, timeout_ms: u64, poll_interval_ms: u64) -> PyResult<Element> {
+        debug!("WinDriver::wait_until_enabled called for xpath: {} (timeout={}ms)", xpath, timeout_ms);
+        poll_until(timeout_ms, poll_interval_ms, || {
+            let element = self.get_ui_element_by_xpath(xpath.clone()).map_err(|_| "element not found".to_string())?;
+            let ui_element = convert_to_ui_element(&element).map_err(|e| format!("{:?}", e))?;
+            match ui_element.is_enabled() {
+                Ok(true) => Ok(element),
+                Ok(false) => Err("element is disabled".to_string()),
+                Err(e) => Err(format!("{:?}", e)),
+            }
+        })
+    }
+
+    /// The HWND of the UI tree's root window, read from the global singleton's tree when
+    /// available so this reflects the most recently captured state.
+    fn root_window_handle(&self) -> PyResult<HWND> {
+        let driver_guard = match WINDRIVER.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                error!("WINDRIVER lock is poisoned, recovering...");
+                poisoned.into_inner()
+            }
+        };
+
+        let ui_tree = if let Some(driver) = driver_guard.as_ref() {
+            &driver.ui_tree
+        } else {
+            warn!("No WinDriver instance in global state, using local tree");
+            &self.ui_tree
+        };
+
+        let (_, root_elem) = ui_tree.node(ui_tree.root());
+        Ok(HWND(root_elem.get_handle() as *mut _))
+    }
+
+    // Region window management methods, operating on the UI tree's root window
+    /// Minimize the root window.
+    pub fn minimize(&self) -> PyResult<()> {
+        debug!("WinDriver::minimize called.");
+        let hwnd = self.root_window_handle()?;
+        unsafe {
+            let _ = ShowWindow(hwnd, SW_MINIMIZE);
+        }
+        PyResult::Ok(())
+    }
+
+    /// Maximize the root window.
+    pub fn maximize(&self) -> PyResult<()> {
+        debug!("WinDriver::maximize called.");
+        let hwnd = self.root_window_handle()?;
+        unsafe {
+            let _ = ShowWindow(hwnd, SW_MAXIMIZE);
+        }
+        PyResult::Ok(())
+    }
+
+    /// Restore the root window if it is currently minimized.
+    pub fn restore(&self) -> PyResult<()> {
+        debug!("WinDriver::restore called.");
+        let hwnd = self.root_window_handle()?;
+        unsafe {
+            if IsIconic(hwnd).as_bool() {
+                let _ = ShowWindow(hwnd, SW_RESTORE);
+            }
+        }
+        PyResult::Ok(())
+    }
+
+    /// Move and/or resize the root window.
+    pub fn move_window(&self, x: i32, y: i32, width: i32, height: i32) -> PyResult<()> {
+        debug!("WinDriver::move_window called with ({}, {}, {}, {})", x, y, width, height);
+        let hwnd = self.root_window_handle()?;
+        unsafe {
+            if let Err(e) = MoveWindow(hwnd, x, y, width, height, true) {
+                error!("Error moving root window: {:?}", e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move window"));
+            }
+        }
+        PyResult::Ok(())
+    }
+
+    /// Bring the root window to the foreground, restoring it first if minimized - the same
+    /// window-normalization sequence pywinauto-recorder's player performs before sending
+    /// input.
+    pub fn bring_to_front(&self) -> PyResult<()> {
+        debug!("WinDriver::bring_to_front called.");
+        let hwnd = self.root_window_handle()?;
+        unsafe {
+            if IsIconic(hwnd).as_bool() {
+                let _ = ShowWindow(hwnd, SW_RESTORE);
+            }
+            if !SetForegroundWindow(hwnd).as_bool() {
+                error!("SetForegroundWindow failed for root window");
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to bring window to front"));
+            }
+        }
+        PyResult::Ok(())
+    }
+
+    /// Pin or unpin the root window as always-on-top.
+    pub fn set_topmost(&self, topmost: bool) -> PyResult<()> {
+        debug!("WinDriver::set_topmost called with topmost={}", topmost);
+        let hwnd = self.root_window_handle()?;
+        let insert_after = if topmost { HWND_TOPMOST } else { HWND_NOTOPMOST };
+        unsafe {
+            if let Err(e) = SetWindowPos(hwnd, Some(insert_after), 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE) {
+                error!("Error setting root window topmost state: {:?}", e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to set window topmost state"));
+            }
+        }
+        PyResult::Ok(())
+    }
+
+    /// Every element whose automation id matches exactly.
+    pub fn find_elements_by_automation_id(&self, automation_id: String) -> PyResult<Vec<Element>> {
+        debug!("WinDriver::find_elements_by_automation_id called for '{}'.", automation_id);
+        let driver_guard = match WINDRIVER.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                error!("WINDRIVER lock is poisoned, recovering...");
+                poisoned.into_inner()
+            }
+        };
+        let ui_tree = if let Some(driver) = driver_guard.as_ref() {
+            &driver.ui_tree
+        } else {
+            warn!("No WinDriver instance in global state, using local tree");
+            &self.ui_tree
+        };
+        PyResult::Ok(locators::find_by_automation_id(ui_tree, &automation_id))
+    }
+
+    /// Every element whose control type matches exactly (case-insensitive).
+    pub fn find_elements_by_control_type(&self, control_type: String) -> PyResult<Vec<Element>> {
+        debug!("WinDriver::find_elements_by_control_type called for '{}'.", control_type);
+        let driver_guard = match WINDRIVER.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                error!("WINDRIVER lock is poisoned, recovering...");
+                poisoned.into_inner()
+            }
+        };
+        let ui_tree = if let Some(driver) = driver_guard.as_ref() {
+            &driver.ui_tree
+        } else {
+            warn!("No WinDriver instance in global state, using local tree");
+            &self.ui_tree
+        };
+        PyResult::Ok(locators::find_by_control_type(ui_tree, &control_type))
+    }
+
+    /// Every element whose name contains `pattern` (case-insensitive substring), or
+    /// matches it as a regex when `use_regex` is set.
+    pub fn find_elements_by_name(&self, pattern: String, use_regex: bool) -> PyResult<Vec<Element>> {
+        debug!("WinDriver::find_elements_by_name called for '{}' (regex={}).", pattern, use_regex);
+        let driver_guard = match WINDRIVER.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                error!("WINDRIVER lock is poisoned, recovering...");
+                poisoned.into_inner()
+            }
+        };
+        let ui_tree = if let Some(driver) = driver_guard.as_ref() {
+            &driver.ui_tree
+        } else {
+            warn!("No WinDriver instance in global state, using local tree");
+            &self.ui_tree
+        };
+        locators::find_by_name(ui_tree, &pattern, use_regex)
+    }
+
+    /// Find the element whose name best matches `text`, scoring every candidate by
+    /// edit-distance similarity and returning the highest scorer above `threshold` (a
+    /// value between 0.0 and 1.0). Errors if the match is ambiguous or no candidate clears
+    /// the threshold, so scripts stay resilient to minor label changes without silently
+    /// picking the wrong element.
+    pub fn find_element_by_best_match(&self, text: String, threshold: f64) -> PyResult<Element> {
+        debug!("WinDriver::find_element_by_best_match called for '{}' (threshold={}).", text, threshold);
+        let driver_guard = match WINDRIVER.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                error!("WINDRIVER lock is poisoned, recovering...");
+                poisoned.into_inner()
+            }
+        };
+        let ui_tree = if let Some(driver) = driver_guard.as_ref() {
+            &driver.ui_tree
+        } else {
+            warn!("No WinDriver instance in global state, using local tree");
+            &self.ui_tree
+        };
+        locators::find_best_match(ui_tree, &text, threshold)
+    }
+
     pub fn get_screen_context(&self) -> PyResult<ScreenContext> {
         debug!("WinDriver::get_screen_context called.");
 
@@ -643,19 +1381,354 @@ impl WinDriver {
     }
 
 
-    /// Launch or activate an application using its path and an XPath
-    /// 
+    /// Capture the monitor at `index` (as ordered by the OS), saving it to `output_path`
+    /// (or a generated path under the temp screenshot directory) and returning the saved
+    /// path alongside the encoded image bytes.
+    ///
+    /// Args:
+    ///     index (int): monitor index, as returned by the OS's monitor enumeration
+    ///     output_path (str | None): where to save the image; auto-generated if None
+    ///     format (str): "png" or "jpeg"
+    ///     quality (int): JPEG quality (0-255); ignored for PNG
+    ///
+    /// Returns:
+    ///     tuple[str, bytes]: the saved file path and the encoded image bytes
+    pub fn capture_monitor(&self, index: usize, output_path: Option<String>, format: String, quality: u8) -> PyResult<(String, Vec<u8>)> {
+        debug!("WinDriver::capture_monitor called for index {}", index);
+        screenshot::capture_monitor(index, output_path, &format, quality)
+    }
+
+    /// Capture every connected monitor, returning the saved path for each.
+    ///
+    /// Args:
+    ///     output_dir (str | None): directory to save images in; the temp screenshot
+    ///         directory is used if None
+    ///     format (str): "png" or "jpeg"
+    ///     quality (int): JPEG quality (0-255); ignored for PNG
+    ///
+    /// Returns:
+    ///     list[str]: the saved file path for each monitor
+    pub fn capture_all_monitors(&self, output_dir: Option<String>, format: String, quality: u8) -> PyResult<Vec<String>> {
+        debug!("WinDriver::capture_all_monitors called");
+        screenshot::capture_all_monitors(output_dir, &format, quality)
+    }
+
+    /// Capture just `element`'s bounding rectangle.
+    ///
+    /// Args:
+    ///     element (Element): the element to capture
+    ///     output_path (str | None): where to save the image; auto-generated if None
+    ///     format (str): "png" or "jpeg"
+    ///     quality (int): JPEG quality (0-255); ignored for PNG
+    ///
+    /// Returns:
+    ///     tuple[str, bytes]: the saved file path and the encoded image bytes
+    pub fn capture_element(&self, element: &Element, output_path: Option<String>, format: String, quality: u8) -> PyResult<(String, Vec<u8>)> {
+        debug!("WinDriver::capture_element called for element: {}", element.get_name());
+        screenshot::capture_element(element, output_path, &format, quality)
+    }
+
+    /// Capture the root window's pixels via `BitBlt`, for failure diagnostics on a
+    /// headless-style automation run. Unlike `capture_monitor`/`capture_all_monitors`,
+    /// this reads straight from the window's device context, so it works even when
+    /// another window is on top.
+    ///
+    /// Args:
+    ///     path (str | None): where to save the PNG; auto-generated under the temp
+    ///         screenshot directory if None
+    ///
+    /// Returns:
+    ///     bytes: the encoded PNG image
+    pub fn capture_screenshot(&self, path: Option<String>) -> PyResult<Vec<u8>> {
+        debug!("WinDriver::capture_screenshot called.");
+        let hwnd = self.root_window_handle()?;
+        let image = capture_hwnd_region(hwnd, None)?;
+        encode_png(&image, path, "window")
+    }
+
+    /// Capture just the element at `xpath`'s bounding rectangle via `BitBlt` from its
+    /// window's device context, so a test failure can be diagnosed against exactly the
+    /// element the XPath resolved to.
+    ///
+    /// Args:
+    ///     xpath (str): XPath identifying the element to capture
+    ///     path (str | None): where to save the PNG; auto-generated under the temp
+    ///         screenshot directory if None
+    ///
+    /// Returns:
+    ///     bytes: the encoded PNG image
+    pub fn capture_element_by_xpath(&self, xpath: String, path: Option<String>) -> PyResult<Vec<u8>> {
+        debug!("WinDriver::capture_element_by_xpath called for xpath: {}", xpath);
+        let element = self.get_ui_element_by_xpath(xpath.clone())?;
+        let hwnd = window_handle(&element);
+        let (left, top, right, bottom) = element.get_bounding_rectangle();
+        let region = RECT { left, top, right, bottom };
+        let image = capture_hwnd_region(hwnd, Some(region))?;
+        encode_png(&image, path, &xpath)
+    }
+
+    /// Every descendant window under `parent_handle`'s text, class name, and handle -
+    /// a pure-Win32 fallback for reaching nested controls (buttons, edit boxes) by class
+    /// or text when UIAutomation is unavailable or too slow, complementing the
+    /// UIAutomation-based `uitree` walk.
+    ///
+    /// Args:
+    ///     parent_handle (int): HWND of the window to enumerate descendants of, e.g. an
+    ///         `Element`'s handle
+    ///
+    /// Returns:
+    ///     list[tuple[str, str, int]]: each descendant's (text, class name, HWND)
+    pub fn enumerate_child_windows(&self, parent_handle: isize) -> PyResult<Vec<(String, String, isize)>> {
+        debug!("WinDriver::enumerate_child_windows called for handle {}", parent_handle);
+        let parent = parent_handle as *mut _;
+        let children = enumerate_child_windows_impl(parent);
+        PyResult::Ok(children.into_iter().map(|(title, class, hwnd)| (title, class, hwnd as isize)).collect())
+    }
+
+    /// `handle`'s bounding rectangle, client rectangle, minimized/maximized state,
+    /// owning process ID, and class name in one call, so a caller can compute a
+    /// control's absolute screen location and feed it into `get_screen_context` rather
+    /// than assuming absolute screen coordinates.
+    ///
+    /// Args:
+    ///     handle (int): HWND of the window to inspect
+    ///
+    /// Returns:
+    ///     WindowInfo: the window's geometry and identity
+    pub fn get_window_info(&self, handle: isize) -> PyResult<WindowInfo> {
+        debug!("WinDriver::get_window_info called for handle {}", handle);
+        let hwnd = handle as *mut _;
+        let info = get_window_info_impl(hwnd).ok_or_else(|| {
+            error!("Could not gather window info for handle {}", handle);
+            pyo3::exceptions::PyValueError::new_err(format!("Window not found for handle {}", handle))
+        })?;
+
+        PyResult::Ok(WindowInfo {
+            bounding_rectangle: (info.left, info.top, info.right, info.bottom),
+            client_rectangle: (info.client_left, info.client_top, info.client_right, info.client_bottom),
+            is_minimized: info.is_minimized,
+            is_maximized: info.is_maximized,
+            pid: info.pid,
+            class_name: info.class_name,
+        })
+    }
+
+    /// Launch or activate an application using its path and an XPath.
+    ///
     /// Args:
     ///     app_path (str): Full path to the application executable
     ///     xpath (str): XPath that identifies an element in the application window
-    /// 
+    ///     args (list[str] | None): extra command-line arguments for a newly spawned process
+    ///     cwd (str | None): working directory for a newly spawned process
+    ///     env (dict[str, str] | None): extra environment variables for a newly spawned process
+    ///     clear_user_data (bool): wipe `user_data_dir` before launch, for a known-clean start
+    ///     user_data_dir (str | None): directory to clear when `clear_user_data` is set
+    ///
     /// Returns:
-    ///     bool: True if the application was successfully launched or activated
-    pub fn launch_or_activate_app(&self, app_path: String, xpath: String) -> PyResult<bool> {
+    ///     LaunchResult: whether the app was launched or activated, and its PID if spawned
+    #[pyo3(signature = (app_path, xpath, args=None, cwd=None, env=None, clear_user_data=false, user_data_dir=None))]
+    pub fn launch_or_activate_app(
+        &mut self,
+        app_path: String,
+        xpath: String,
+        args: Option<Vec<String>>,
+        cwd: Option<String>,
+        env: Option<HashMap<String, String>>,
+        clear_user_data: bool,
+        user_data_dir: Option<String>,
+    ) -> PyResult<LaunchResult> {
         debug!("WinDriver::launch_or_activate_app called with {} as app path and {} as xpath element.", app_path, xpath);
 
-        let result = launch_or_activate_application(&app_path, &xpath);
-        PyResult::Ok(result)
+        let args = args.unwrap_or_default();
+        let outcome = launch_or_activate_application(
+            &app_path,
+            &xpath,
+            &args,
+            cwd.as_deref(),
+            env.as_ref(),
+            clear_user_data,
+            user_data_dir.as_deref(),
+        );
+        if outcome.pid.is_some() {
+            self.launched_pid = outcome.pid;
+            match WINDRIVER.lock() {
+                Ok(mut guard) => {
+                    *guard = Some(self.clone());
+                }
+                Err(poisoned) => {
+                    error!("WINDRIVER lock is poisoned, recovering...");
+                    let mut guard = poisoned.into_inner();
+                    *guard = Some(self.clone());
+                }
+            }
+        }
+
+        PyResult::Ok(LaunchResult { success: outcome.success, pid: outcome.pid })
+    }
+
+    /// Like `launch_or_activate_app`, but with a configurable activation strategy and an
+    /// outcome that separates confirmed from unconfirmed activation, a fresh launch, and
+    /// no match, instead of a single success flag, so the caller can retry or abort
+    /// rather than silently proceeding on a failed focus.
+    ///
+    /// Args:
+    ///     app_path (str): full path to the application executable
+    ///     xpath (str): XPath identifying an element in the application window
+    ///     args (list[str] | None): extra command-line arguments for a newly spawned process
+    ///     cwd (str | None): working directory for a newly spawned process
+    ///     env (dict[str, str] | None): extra environment variables for a newly spawned process
+    ///     confirm_timeout_ms (int): how long to wait for the foreground switch to be
+    ///         confirmed before reporting it as unconfirmed
+    ///     use_alt_key_unlock (bool): whether to fall back to the Alt-key
+    ///         foreground-unlock trick if the direct activation attempts don't take effect
+    ///     match_mode (str | None): "exact", "partial", "class", or "pid" - how to match
+    ///         an already-running window; defaults to "exact"
+    ///
+    /// Returns:
+    ///     tuple[str, int | None]: one of "already_foreground", "activated",
+    ///     "activated_unconfirmed", "not_found", "launched_new", paired with the PID when
+    ///     a new process was launched
+    #[pyo3(signature = (app_path, xpath, args=None, cwd=None, env=None, confirm_timeout_ms=100, use_alt_key_unlock=true, match_mode=None))]
+    pub fn activate_or_launch_app(
+        &mut self,
+        app_path: String,
+        xpath: String,
+        args: Option<Vec<String>>,
+        cwd: Option<String>,
+        env: Option<HashMap<String, String>>,
+        confirm_timeout_ms: u64,
+        use_alt_key_unlock: bool,
+        match_mode: Option<String>,
+    ) -> PyResult<(String, Option<u32>)> {
+        debug!("WinDriver::activate_or_launch_app called with {} as app path and {} as xpath element.", app_path, xpath);
+
+        let match_mode = WindowMatchMode::parse(match_mode.as_deref().unwrap_or("exact"))
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let options = ActivationOptions { confirm_timeout_ms, use_alt_key_unlock, match_mode };
+        let args = args.unwrap_or_default();
+
+        let result = activate_or_launch_with_options(&app_path, &xpath, &args, cwd.as_deref(), env.as_ref(), &options);
+
+        if let Some(pid) = result.pid() {
+            self.launched_pid = Some(pid);
+            match WINDRIVER.lock() {
+                Ok(mut guard) => {
+                    *guard = Some(self.clone());
+                }
+                Err(poisoned) => {
+                    error!("WINDRIVER lock is poisoned, recovering...");
+                    let mut guard = poisoned.into_inner();
+                    *guard = Some(self.clone());
+                }
+            }
+        }
+
+        PyResult::Ok((result.as_str().to_string(), result.pid()))
+    }
+
+    /// Whether the process most recently spawned by `launch_or_activate_app` is still
+    /// running.
+    ///
+    /// Returns:
+    ///     bool: True if the process is alive
+    pub fn is_app_running(&self) -> PyResult<bool> {
+        debug!("WinDriver::is_app_running called.");
+        let pid = self.launched_pid.ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err("No application has been launched by this driver")
+        })?;
+
+        unsafe {
+            let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).map_err(|e| {
+                error!("Failed to open launched process {}: {:?}", pid, e);
+                pyo3::exceptions::PyValueError::new_err("Failed to open launched process")
+            })?;
+
+            let mut exit_code = 0u32;
+            let result = GetExitCodeProcess(handle, &mut exit_code);
+            let _ = CloseHandle(handle);
+
+            match result {
+                Ok(_) => PyResult::Ok(exit_code == STILL_ACTIVE.0 as u32),
+                Err(e) => {
+                    error!("Failed to query exit code for process {}: {:?}", pid, e);
+                    PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to query process state"))
+                }
+            }
+        }
+    }
+
+    /// Block until the process spawned by `launch_or_activate_app` exits, or `timeout_ms`
+    /// elapses. Implemented with `WaitForSingleObject`/`GetExitCodeProcess` on the process
+    /// handle, reaping it (clearing the tracked PID) once it is observed to have exited -
+    /// mirroring the waitable-process design async-process uses to collect a child's exit
+    /// status exactly once.
+    ///
+    /// Args:
+    ///     timeout_ms (int): maximum time to wait, in milliseconds (0 waits indefinitely)
+    ///
+    /// Returns:
+    ///     int | None: the process's exit code, or None if `timeout_ms` elapsed first
+    pub fn wait_for_exit(&mut self, timeout_ms: u64) -> PyResult<Option<i32>> {
+        debug!("WinDriver::wait_for_exit called (timeout_ms={}).", timeout_ms);
+        let pid = self.launched_pid.ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err("No application has been launched by this driver")
+        })?;
+
+        unsafe {
+            let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_SYNCHRONIZE, false, pid).map_err(|e| {
+                error!("Failed to open launched process {}: {:?}", pid, e);
+                pyo3::exceptions::PyValueError::new_err("Failed to open launched process")
+            })?;
+
+            let wait_ms = if timeout_ms == 0 { INFINITE } else { timeout_ms as u32 };
+            let wait_result = WaitForSingleObject(handle, wait_ms);
+
+            let outcome = if wait_result == WAIT_OBJECT_0 {
+                let mut exit_code = 0u32;
+                let _ = GetExitCodeProcess(handle, &mut exit_code);
+                self.launched_pid = None;
+                info!("Process {} exited with code {}", pid, exit_code);
+                PyResult::Ok(Some(exit_code as i32))
+            } else if wait_result == WAIT_TIMEOUT {
+                PyResult::Ok(None)
+            } else {
+                error!("WaitForSingleObject failed for process {}: {:?}", pid, wait_result);
+                PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to wait for process exit"))
+            };
+
+            let _ = CloseHandle(handle);
+            outcome
+        }
+    }
+
+    /// Forcibly terminate the process spawned by `launch_or_activate_app`, if any, and
+    /// stop tracking it.
+    ///
+    /// Returns:
+    ///     None
+    pub fn terminate_app(&mut self) -> PyResult<()> {
+        debug!("WinDriver::terminate_app called.");
+        let pid = self.launched_pid.take().ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err("No application has been launched by this driver")
+        })?;
+
+        unsafe {
+            let handle = OpenProcess(PROCESS_TERMINATE, false, pid).map_err(|e| {
+                error!("Failed to open launched process {} for termination: {:?}", pid, e);
+                pyo3::exceptions::PyValueError::new_err("Failed to open launched process for termination")
+            })?;
+
+            let result = TerminateProcess(handle, 1);
+            let _ = CloseHandle(handle);
+
+            if let Err(e) = result {
+                error!("Failed to terminate process {}: {:?}", pid, e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to terminate process"));
+            }
+        }
+
+        info!("Terminated process {}", pid);
+        PyResult::Ok(())
     }
 
     /// Refresh the UI tree to capture the current state of the screen
@@ -705,6 +1778,277 @@ impl WinDriver {
         PyResult::Ok(())
     }
 
+    /// Poll for an element matching `xpath`, re-capturing the UI tree between attempts,
+    /// until it appears or `timeout_ms` elapses. Unlike `wait_for_element_by_xpath` (which
+    /// polls the tree already in memory on a fixed sleep), this re-runs the tree capture
+    /// each attempt and wakes early from a StructureChanged event instead of always
+    /// sleeping the full `poll_ms` interval.
+    ///
+    /// Args:
+    ///     xpath (str): XPath identifying the element to wait for
+    ///     timeout_ms (int): Maximum time to wait, in milliseconds
+    ///     poll_ms (int): Maximum delay between re-checks, in milliseconds
+    ///
+    /// Returns:
+    ///     bool: True once the element is found
+    pub fn wait_for_element(&mut self, xpath: String, timeout_ms: u64, poll_ms: u64) -> PyResult<bool> {
+        debug!("WinDriver::wait_for_element called for xpath: {} (timeout={}ms, poll={}ms)", xpath, timeout_ms, poll_ms);
+
+        let start = Instant::now();
+        let overall_timeout = Duration::from_millis(timeout_ms);
+
+        loop {
+            if self.get_ui_element_by_xpath(xpath.clone()).is_ok() {
+                info!("Element found for xpath '{}' after {:?}", xpath, start.elapsed());
+                return Ok(true);
+            }
+
+            let remaining = overall_timeout.saturating_sub(start.elapsed());
+            if remaining.is_zero() {
+                error!("Timed out after {}ms waiting for element with xpath '{}'", timeout_ms, xpath);
+                return Err(pyo3::exceptions::PyTimeoutError::new_err(format!(
+                    "Timed out after {}ms waiting for element with xpath '{}'", timeout_ms, xpath
+                )));
+            }
+
+            // Wait for either a StructureChanged event or `poll_ms`, whichever comes first,
+            // so a fast-loading window doesn't sit through a full poll interval.
+            let dirty_guard = match events::TREE_DIRTY.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let wait_for = Duration::from_millis(poll_ms.max(1)).min(remaining);
+            let (mut dirty, _) = match events::TREE_DIRTY_CONDVAR.wait_timeout(dirty_guard, wait_for) {
+                Ok(result) => result,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            *dirty = false;
+            drop(dirty);
+
+            self.refresh()?;
+        }
+    }
+
+    /// Block until the UI has been quiet for `quiet_ms` - i.e. no StructureChanged or
+    /// watched AutomationPropertyChanged event has arrived - then rebuild the tree exactly
+    /// once. This replaces "refresh and hope it was enough time" with a wait driven by the
+    /// actual UIA events `events::ensure_registered` subscribes to at driver construction.
+    ///
+    /// Args:
+    ///     quiet_ms (int): how long the UI must stay quiet before the tree is rebuilt
+    ///     timeout_ms (int): maximum total time to wait for that quiet window
+    ///
+    /// Returns:
+    ///     None
+    pub fn wait_until_stable(&mut self, quiet_ms: u64, timeout_ms: u64) -> PyResult<()> {
+        debug!("WinDriver::wait_until_stable called (quiet_ms={}, timeout_ms={})", quiet_ms, timeout_ms);
+
+        let start = Instant::now();
+        let overall_timeout = Duration::from_millis(timeout_ms);
+        let quiet = Duration::from_millis(quiet_ms);
+
+        loop {
+            let remaining = overall_timeout.saturating_sub(start.elapsed());
+            if remaining.is_zero() {
+                error!("Timed out after {}ms waiting for the UI to settle", timeout_ms);
+                return Err(pyo3::exceptions::PyTimeoutError::new_err(format!(
+                    "Timed out after {}ms waiting for the UI to settle", timeout_ms
+                )));
+            }
+
+            let dirty_guard = match events::TREE_DIRTY.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+
+            let (mut dirty, wait_result) = match events::TREE_DIRTY_CONDVAR.wait_timeout(dirty_guard, quiet.min(remaining)) {
+                Ok(result) => result,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+
+            if wait_result.timed_out() && !*dirty {
+                // No event arrived for a full quiet window - the UI has settled.
+                drop(dirty);
+                info!("UI settled after {:?}, rebuilding tree", start.elapsed());
+                return self.refresh();
+            }
+
+            // An event arrived during the quiet window (or we were spuriously woken with
+            // the flag already set) - clear it and keep waiting for the next quiet window.
+            *dirty = false;
+        }
+    }
+
+    /// Start capturing a replayable script of actions performed against resolved
+    /// `Element`s (clicks, key presses, text entry), discarding any previously recorded
+    /// steps.
+    ///
+    /// Returns:
+    ///     None
+    pub fn start_recording(&mut self) -> PyResult<()> {
+        debug!("WinDriver::start_recording called.");
+        self.recording = true;
+        self.recorded_steps.clear();
+        self.last_record_instant = None;
+
+        match WINDRIVER.lock() {
+            Ok(mut guard) => {
+                *guard = Some(self.clone());
+            }
+            Err(poisoned) => {
+                error!("WINDRIVER lock is poisoned, recovering...");
+                let mut guard = poisoned.into_inner();
+                *guard = Some(self.clone());
+            }
+        }
+
+        info!("Recording started");
+        PyResult::Ok(())
+    }
+
+    /// Stop capturing actions and return the recorded script as JSON, suitable for
+    /// `replay()`.
+    ///
+    /// Returns:
+    ///     str: the recorded script, serialized as JSON
+    pub fn stop_recording(&mut self) -> PyResult<String> {
+        debug!("WinDriver::stop_recording called.");
+        self.recording = false;
+
+        // Steps are appended to the global singleton by `record_step` (the same one
+        // `convert_to_ui_element` keeps up to date), so pull the accumulated steps from
+        // there rather than from this possibly-stale local instance.
+        let mut driver_guard = match WINDRIVER.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                error!("WINDRIVER lock is poisoned, recovering...");
+                poisoned.into_inner()
+            }
+        };
+
+        self.recorded_steps = match driver_guard.as_mut() {
+            Some(driver) => {
+                driver.recording = false;
+                std::mem::take(&mut driver.recorded_steps)
+            }
+            None => std::mem::take(&mut self.recorded_steps),
+        };
+        drop(driver_guard);
+
+        serde_json::to_string(&self.recorded_steps).map_err(|e| {
+            error!("Failed to serialize recorded steps: {:?}", e);
+            pyo3::exceptions::PyValueError::new_err("Failed to serialize recorded script")
+        })
+    }
+
+    /// Whether a recording is currently in progress.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Replay a script previously produced by `stop_recording`, scaling the recorded
+    /// delay between steps by `1 / speed_factor` (a `speed_factor` of 2.0 replays twice as
+    /// fast). Each step re-captures the UI tree and re-resolves its element by XPath
+    /// rather than trusting stale coordinates, so replay survives the target window having
+    /// moved or resized since recording. Left, right and double clicks, wheel and move
+    /// actions are synthesized against the element's freshly observed bounding-rectangle
+    /// center via absolute `mouse_event` calls, the same coordinate-independent approach
+    /// pywinauto-recorder's player uses; hold-clicks and keyboard actions still dispatch
+    /// through the resolved `UIElement`.
+    ///
+    /// Args:
+    ///     script (str): a JSON script produced by `stop_recording`
+    ///     speed_factor (float): playback speed multiplier
+    ///     settle_ms (int): extra delay after each step, letting the UI catch up before
+    ///         the next one re-resolves its element
+    ///
+    /// Returns:
+    ///     None
+    #[pyo3(signature = (script, speed_factor, settle_ms=0))]
+    pub fn replay(&mut self, script: String, speed_factor: f64, settle_ms: u64) -> PyResult<()> {
+        debug!("WinDriver::replay called with speed_factor={} settle_ms={}", speed_factor, settle_ms);
+
+        let steps: Vec<RecordedStep> = serde_json::from_str(&script).map_err(|e| {
+            error!("Failed to parse replay script: {:?}", e);
+            pyo3::exceptions::PyValueError::new_err("Invalid replay script")
+        })?;
+
+        let speed_factor = if speed_factor > 0.0 { speed_factor } else { 1.0 };
+
+        for step in steps {
+            let delay = Duration::from_millis((step.delay_ms as f64 / speed_factor) as u64);
+            if !delay.is_zero() {
+                thread::sleep(delay);
+            }
+
+            self.refresh()?;
+            let element = self.get_ui_element_by_xpath(step.xpath.clone()).map_err(|e| {
+                error!("Replay could not resolve element for xpath '{}': {:?}", step.xpath, e);
+                e
+            })?;
+
+            let result: Result<(), String> = match &step.action {
+                RecordedAction::LeftClick => {
+                    let (x, y) = element_center(&element);
+                    synthesize_click(x, y, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP);
+                    Ok(())
+                }
+                RecordedAction::RightClick => {
+                    let (x, y) = element_center(&element);
+                    synthesize_click(x, y, MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP);
+                    Ok(())
+                }
+                RecordedAction::DoubleClick => {
+                    let (x, y) = element_center(&element);
+                    synthesize_click(x, y, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP);
+                    synthesize_click(x, y, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP);
+                    Ok(())
+                }
+                RecordedAction::Move => {
+                    let (x, y) = element_center(&element);
+                    let (ax, ay) = to_absolute(x, y);
+                    unsafe {
+                        mouse_event(MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_MOVE, ax, ay, 0, 0);
+                    }
+                    Ok(())
+                }
+                RecordedAction::Wheel { notches, horizontal } => {
+                    let (x, y) = element_center(&element);
+                    let (ax, ay) = to_absolute(x, y);
+                    unsafe {
+                        mouse_event(MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_MOVE, ax, ay, 0, 0);
+                        let flag = if *horizontal { MOUSEEVENTF_HWHEEL } else { MOUSEEVENTF_WHEEL };
+                        mouse_event(flag, 0, 0, (notches * WHEEL_DELTA) as u32, 0);
+                    }
+                    Ok(())
+                }
+                RecordedAction::HoldClick { holdkeys } => {
+                    convert_to_ui_element(&element).and_then(|e| e.hold_click(holdkeys)).map_err(|e| format!("{:?}", e))
+                }
+                RecordedAction::SendKeys { keys } => {
+                    convert_to_ui_element(&element).and_then(|e| e.send_keys(keys, 20)).map_err(|e| format!("{:?}", e))
+                }
+                RecordedAction::SendText { text } => {
+                    convert_to_ui_element(&element).and_then(|e| e.send_text(text, 20)).map_err(|e| format!("{:?}", e))
+                }
+            };
+
+            if let Err(e) = result {
+                error!("Replay step failed for xpath '{}': {}", step.xpath, e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Replay step failed for xpath '{}': {}", step.xpath, e
+                )));
+            }
+
+            if settle_ms > 0 {
+                thread::sleep(Duration::from_millis(settle_ms));
+            }
+        }
+
+        info!("Replay finished successfully");
+        PyResult::Ok(())
+    }
+
     /// Close the WinDriver instance and free the global singleton
     ///
     /// This method clears the global WinDriver instance, allowing a new
@@ -717,6 +2061,28 @@ impl WinDriver {
     pub fn close(&mut self) -> PyResult<()> {
         debug!("WinDriver::close called.");
 
+        // Reap any still-tracked launched process so a terminated-but-unwaited child
+        // doesn't leave a dangling PID behind once this driver is gone.
+        if let Some(pid) = self.launched_pid.take() {
+            unsafe {
+                match OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) {
+                    Ok(handle) => {
+                        let mut exit_code = 0u32;
+                        let _ = GetExitCodeProcess(handle, &mut exit_code);
+                        let _ = CloseHandle(handle);
+                        if exit_code == STILL_ACTIVE.0 as u32 {
+                            warn!("Closing driver while launched process {} is still running", pid);
+                        } else {
+                            debug!("Reaped launched process {} (exit code {})", pid, exit_code);
+                        }
+                    }
+                    Err(e) => {
+                        debug!("Launched process {} already gone at close: {:?}", pid, e);
+                    }
+                }
+            }
+        }
+
         // Clear the global WINDRIVER instance
         match WINDRIVER.lock() {
             Ok(mut guard) => {
@@ -737,4 +2103,47 @@ impl WinDriver {
 
 fn normalized(filename: String) -> String {
     filename.replace(['|', '\\', ':', '/'], "")
+}
+
+/// The best available text for an element: its value pattern when it has one (edit boxes,
+/// combo boxes, ...), falling back to its name otherwise.
+fn current_text(ui_element: &UIElement) -> String {
+    if let Ok(pattern) = ui_element.get_pattern::<uiautomation::patterns::UIValuePattern>() {
+        if let Ok(value) = pattern.get_value() {
+            return value;
+        }
+    }
+    ui_element.get_name().unwrap_or_default()
+}
+
+/// Re-run `attempt` on a fixed interval until it succeeds or `timeout_ms` elapses, always
+/// evaluating at least once even when `timeout_ms` is 0. On timeout, the last error
+/// `attempt` returned (e.g. the last-seen text or enabled state) is folded into the
+/// `PyTimeoutError` message so callers can see what was actually observed.
+fn poll_until<T, F>(timeout_ms: u64, poll_interval_ms: u64, mut attempt: F) -> PyResult<T>
+where
+    F: FnMut() -> Result<T, String>,
+{
+    let start = Instant::now();
+    let timeout = Duration::from_millis(timeout_ms);
+    let interval = Duration::from_millis(poll_interval_ms.max(1));
+    let mut last_seen = String::new();
+
+    loop {
+        match attempt() {
+            Ok(value) => return Ok(value),
+            Err(e) => last_seen = e,
+        }
+
+        if start.elapsed() >= timeout {
+            break;
+        }
+
+        thread::sleep(interval.min(timeout.saturating_sub(start.elapsed())).max(Duration::from_millis(1)));
+    }
+
+    Err(pyo3::exceptions::PyTimeoutError::new_err(format!(
+        "Timed out after {}ms. Last seen: {}",
+        timeout_ms, last_seen
+    )))
 }
\ No newline at end of file
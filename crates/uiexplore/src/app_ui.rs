@@ -204,7 +204,7 @@ enum AppMode {
 }
 
 #[derive(PartialEq)]
-enum DisplayMode { Explore, XpathTest }
+enum DisplayMode { Explore, XpathTest, Search }
 
 
 // #[allow(dead_code)]
@@ -225,6 +225,15 @@ pub struct UIExplorer {
     app_mode: AppMode,
     display_mode: DisplayMode,
     winevent_monitor: WinEventMonitor,
+    overlay: rectangle::OverlayState,
+    keybindings: crate::keybindings::KeyBindings,
+    search_input: String,
+    search_regex: Option<(String, regex::Regex)>,
+    search_matches: Vec<usize>,
+    search_current: Option<usize>,
+    hints: crate::hints::HintState,
+    ipc_rx: Receiver<crate::ipc::IpcRequest>,
+    vi_nav: bool,
 }
 
 impl UIExplorer {
@@ -258,6 +267,15 @@ impl UIExplorer {
             app_mode: AppMode::Normal(LastRefresh { time: std::time::Instant::now() }),
             display_mode: DisplayMode::Explore,
             winevent_monitor: WinEventMonitor::new(),
+            overlay: rectangle::OverlayState::new(),
+            keybindings: crate::keybindings::KeyBindings::load("keybindings.json"),
+            search_input: String::new(),
+            search_regex: None,
+            search_matches: Vec::new(),
+            search_current: None,
+            hints: crate::hints::HintState::new(),
+            ipc_rx: crate::ipc::spawn_ipc_server(crate::ipc::PIPE_NAME),
+            vi_nav: false,
         }
 
 
@@ -283,6 +301,15 @@ impl UIExplorer {
             app_mode: AppMode::Normal(LastRefresh { time: std::time::Instant::now() }),
             display_mode: DisplayMode::Explore,
             winevent_monitor: WinEventMonitor::new(),
+            overlay: rectangle::OverlayState::new(),
+            keybindings: crate::keybindings::KeyBindings::load("keybindings.json"),
+            search_input: String::new(),
+            search_regex: None,
+            search_matches: Vec::new(),
+            search_current: None,
+            hints: crate::hints::HintState::new(),
+            ipc_rx: crate::ipc::spawn_ipc_server(crate::ipc::PIPE_NAME),
+            vi_nav: false,
         }
     }
 
@@ -471,7 +498,7 @@ impl UIExplorer {
                     }
 
                     // update the actual active element
-                    self.process_event(event, &mut state);
+                    self.process_event(ctx, event, &mut state);
                 }
             });
     
@@ -481,12 +508,20 @@ impl UIExplorer {
                 ui.label("Mode: ");
                 ui.radio_value(&mut self.display_mode, DisplayMode::Explore, "Explore");
                 ui.radio_value(&mut self.display_mode, DisplayMode::XpathTest, "Test Xpath");
+                ui.radio_value(&mut self.display_mode, DisplayMode::Search, "Search");
 
                 match self.display_mode {
                     DisplayMode::XpathTest => {
                         //skip rendering further options
                     },
 
+                    DisplayMode::Search => {
+                        ui.add_space(2.0);
+                        ui.label(" | ");
+                        ui.add_space(2.0);
+                        ui.checkbox(&mut self.highlighting, "Show Highlight Rectangle");
+                    },
+
                     DisplayMode::Explore => {
 
                         ui.add_space(2.0);
@@ -542,6 +577,7 @@ impl UIExplorer {
                                 bottom: self.app_context.screen_height, 
                             };
                             rectangle::clear_frame(rect).unwrap();
+                            self.overlay.reset();
                             state.clear_frame = false;
                         }                        
                         
@@ -553,7 +589,7 @@ impl UIExplorer {
             ui.add_space(4.0);
 
             match self.display_mode {
-                DisplayMode::XpathTest => {
+                DisplayMode::XpathTest | DisplayMode::Search => {
                     // skip rendering of the history
                 },
                 DisplayMode::Explore => {
@@ -612,42 +648,26 @@ impl UIExplorer {
                     
                     // Optionally render the frame around the active element on the screen
                     if self.highlighting {
-                        let left: f32 = active_element.get_element().get_bounding_rectangle().get_left() as f32 * self.app_context.screen_scale;
-                        let top: f32 = active_element.get_element().get_bounding_rectangle().get_top() as f32 * self.app_context.screen_scale;
-                        let right: f32 = active_element.get_element().get_bounding_rectangle().get_right() as f32 * self.app_context.screen_scale;
-                        let bottom: f32 = active_element.get_element().get_bounding_rectangle().get_bottom() as f32 * self.app_context.screen_scale;
-
-                        let rect: RECT = RECT { 
-                            left: left as i32, 
-                            top: top as i32, 
-                            right: right as i32, 
-                            bottom: bottom as i32, 
+                        let bounding_rectangle = active_element.get_element().get_bounding_rectangle();
+                        let scale = crate::monitors::scale_for_rect(&bounding_rectangle);
+                        let left: f32 = bounding_rectangle.get_left() as f32 * scale;
+                        let top: f32 = bounding_rectangle.get_top() as f32 * scale;
+                        let right: f32 = bounding_rectangle.get_right() as f32 * scale;
+                        let bottom: f32 = bounding_rectangle.get_bottom() as f32 * scale;
+
+                        let rect: RECT = RECT {
+                            left: left as i32,
+                            top: top as i32,
+                            right: right as i32,
+                            bottom: bottom as i32,
                         };
-                        
-                        if let Some(prev_element) = &state.prev_element {
-                            let prev_left: f32 = prev_element.get_element().get_bounding_rectangle().get_left() as f32 * self.app_context.screen_scale;
-                            let prev_top: f32 = prev_element.get_element().get_bounding_rectangle().get_top() as f32 * self.app_context.screen_scale;
-                            let prev_right: f32 = prev_element.get_element().get_bounding_rectangle().get_right() as f32 * self.app_context.screen_scale;
-                            let prev_bottom: f32 = prev_element.get_element().get_bounding_rectangle().get_bottom() as f32 * self.app_context.screen_scale;
-
-                            let prev_rect: RECT = RECT {
-                                left: prev_left as i32, 
-                                top: prev_top as i32, 
-                                right: prev_right as i32, 
-                                bottom: prev_bottom as i32,     
-                            };
-                            if state.clear_frame { //rect != prev_rect && 
-                                printfmt!("Cleanup needed - new: {:?} vs old: {:?}", rect, prev_rect);
-                                rectangle::clear_frame(prev_rect).unwrap();
-                                rectangle::draw_frame(rect, 4).unwrap();
-                                state.clear_frame = false;
-                            } else {
-                                rectangle::draw_frame(rect, 4).unwrap();
-                            }
-                        } else {
-                            rectangle::draw_frame(rect, 4).unwrap();
-                        }
-                    } 
+
+                        // OverlayState skips the repaint entirely when the rect hasn't
+                        // moved, and only invalidates the union of the old/new rects
+                        // when it has, instead of redrawing unconditionally every frame.
+                        self.overlay.update(rect, 4).unwrap();
+                        state.clear_frame = false;
+                    }
                     
                     // display the element properties 
                     egui::Grid::new("some_unique_id").min_col_width(100.0).max_col_width(800.0)
@@ -824,50 +844,355 @@ impl UIExplorer {
                     });
 
                 }
-            } 
+            }
         });
     }
 
+    #[inline(always)]
+    fn render_search_screen(&mut self, ctx: &egui::Context, state: &mut TreeState) {
+
+        let screen_size = ctx.screen_rect();
+        let elem_width = screen_size.width() * 0.9;
+
+        egui::TopBottomPanel::top("search_panel").resizable(false).show(ctx, |ui| {
+            ui.add_space(4.0);
+
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.search_input)
+                    .hint_text("Enter a regex to search by name, control type, class name or automation id")
+                    .desired_width(elem_width)
+            );
+
+            if response.changed() {
+                self.run_search();
+            }
+
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let match_count = self.search_matches.len();
+
+                if self.search_regex.is_some() {
+                    ui.label(format!("{} match{}", match_count, if match_count == 1 { "" } else { "es" }));
+                } else if !self.search_input.is_empty() {
+                    ui.colored_label(egui::Color32::RED, "Invalid regex");
+                }
+
+                if ui.button("Previous").clicked() {
+                    self.goto_match(-1, state);
+                }
+                if ui.button("Next").clicked() {
+                    self.goto_match(1, state);
+                }
+
+                if let Some(current) = self.search_current {
+                    ui.label(format!("{} / {}", current + 1, match_count));
+                }
+            });
+            ui.add_space(4.0);
+        });
+    }
+
+    /// Recompile the search pattern only when it actually changed since the last frame, so
+    /// typing into a large element tree does not re-run the regex over every node on every
+    /// keystroke redraw.
+    fn run_search(&mut self) {
+        let pattern = self.search_input.clone();
+
+        if pattern.is_empty() {
+            self.search_regex = None;
+            self.search_matches.clear();
+            self.search_current = None;
+            return;
+        }
+
+        let already_compiled = self.search_regex.as_ref().is_some_and(|(cached, _)| cached == &pattern);
+        if !already_compiled {
+            match regex::Regex::new(&pattern) {
+                Ok(re) => self.search_regex = Some((pattern, re)),
+                Err(_) => {
+                    self.search_regex = None;
+                    self.search_matches.clear();
+                    self.search_current = None;
+                    return;
+                }
+            }
+        }
+
+        if let Some((_, re)) = &self.search_regex {
+            self.search_matches = self.ui_tree.filter_by_regex(re);
+            self.search_current = None;
+        }
+    }
+
+    /// Move the current match by `delta` (wrapping), and push the new match into `state`
+    /// exactly as if the user had hovered it in the Explore view.
+    fn goto_match(&mut self, delta: isize, state: &mut TreeState) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+
+        let len = self.search_matches.len() as isize;
+        let next = match self.search_current {
+            Some(current) => (current as isize + delta).rem_euclid(len) as usize,
+            None if delta >= 0 => 0,
+            None => (len - 1) as usize,
+        };
+        self.search_current = Some(next);
+
+        let index = self.search_matches[next];
+        let (_, element) = self.ui_tree.node(index);
+        state.update_state(element.clone(), index);
+    }
+
 
     #[inline(always)]
-    fn process_event(&mut self, event: &egui::Event, state: &mut TreeState) {
-        
+    fn process_event(&mut self, ctx: &egui::Context, event: &egui::Event, state: &mut TreeState) {
+
         match event {
-            egui::Event::MouseMoved { .. } => { 
+            egui::Event::MouseMoved { .. } => {
                 // printfmt!("Mouse moved event received");
                 // printfmt!("Getting cursor position");
 
                 let cursor_position = unsafe {
                     let mut cursor_pos = POINT::default();
                     GetCursorPos(&mut cursor_pos).unwrap();
-                    cursor_pos.x = (cursor_pos.x as f32 / self.app_context.screen_scale) as i32;
-                    cursor_pos.y = (cursor_pos.y as f32 / self.app_context.screen_scale) as i32;
+                    let scale = crate::monitors::scale_for_point(cursor_pos.x, cursor_pos.y);
+                    cursor_pos.x = (cursor_pos.x as f32 / scale) as i32;
+                    cursor_pos.y = (cursor_pos.y as f32 / scale) as i32;
                     cursor_pos
                 };
                 // printfmt!("getting bouding rectangle for cursor position: ({}, {})", cursor_position.x, cursor_position.y);
                 // printfmt!("Searching {} elements in the UI tree", self.ui_tree.get_elements().len());
-                if let Some(ui_element_props) = rectangle::get_point_bounding_rect(&cursor_position, self.ui_tree.get_elements()) {
+                if let Some((_, ui_element_props)) = rectangle::get_point_bounding_rect(&cursor_position, self.ui_tree.get_elements()) {
                     // printfmt!("Updating state with element found at cursor position: {}", ui_element_props.get_element_props().get_element().get_name());
                     state.update_state(ui_element_props.get_element_props().clone(), ui_element_props.get_tree_index());
-                } 
+                }
             }
-            egui::Event::Key { key, pressed, ..} => { // physical_key, repeat, modifiers 
+            egui::Event::Key { key, pressed, modifiers, .. } => { // physical_key, repeat
                 // printfmt!("Key event received: {:?}, pressed: {}", key, pressed);
-                if key == &egui::Key::Escape && !*pressed  {                 
-                    // check if tracking is enabled, if yes, desable tracking
-                    // if not, ignore the escape key
-                    if self.recording == true {
-                        self.recording = false;
-                        self.set_status("Tracking disabled".to_string(), Duration::seconds(2));
-                    } else {
-                        self.set_status("No tracking active, ignoring Escape key".to_string(), Duration::seconds(2));
+                if !*pressed {
+                    return;
+                }
+
+                // While hint mode is active, keystrokes type into the hint label instead
+                // of going through the normal key bindings - Escape is the one exception,
+                // which always cancels it.
+                if self.hints.is_active() {
+                    if *key == egui::Key::Escape {
+                        self.hints.cancel();
+                        self.set_status("Hint mode cancelled".to_string(), Duration::seconds(2));
+                        return;
+                    }
+
+                    if let Some(c) = hint_char(*key) {
+                        match self.hints.type_char(c) {
+                            crate::hints::HintOutcome::Narrowed => {}
+                            crate::hints::HintOutcome::Matched(tree_index) => {
+                                let (_, element) = self.ui_tree.node(tree_index);
+                                state.update_state(element.clone(), tree_index);
+                                self.set_status("Jumped to hinted element".to_string(), Duration::seconds(2));
+                            }
+                            crate::hints::HintOutcome::NoMatch => {
+                                self.set_status("No element with that hint".to_string(), Duration::seconds(2));
+                            }
+                        }
                     }
+                    return;
+                }
+
+                // While vi-navigation mode is active, h/j/k/l and friends move the
+                // active element along the tree instead of going through the normal
+                // key bindings - Escape is the one exception, which always leaves it.
+                if self.vi_nav {
+                    if *key == egui::Key::Escape {
+                        self.vi_nav = false;
+                        self.set_status("Vi navigation disabled".to_string(), Duration::seconds(2));
+                        return;
+                    }
+
+                    if let Some(motion) = vi_motion_for_key(*key) {
+                        self.apply_vi_motion(motion, state);
+                        return;
+                    }
+                }
+
+                if let Some(action) = self.keybindings.action_for(*key, *modifiers) {
+                    self.dispatch_action(action, ctx, state);
                 }
             }
             _ => (),
         }
     }
 
+    /// Execute the semantic `Action` a key binding resolved to, against `&mut self`/`state`.
+    fn dispatch_action(&mut self, action: crate::keybindings::Action, ctx: &egui::Context, state: &TreeState) {
+        use crate::keybindings::Action;
+
+        match action {
+            Action::ToggleRecording => {
+                if self.recording {
+                    self.recording = false;
+                    self.set_status("Tracking disabled".to_string(), Duration::seconds(2));
+                } else {
+                    self.recording = true;
+                    self.set_status("Tracking enabled".to_string(), Duration::seconds(2));
+                }
+            }
+            Action::RefreshTree => {
+                self.app_mode = AppMode::NeedsTreeRefresh;
+            }
+            Action::CopyXPath => {
+                match state.active_ui_element {
+                    Some(active_ui_element) => {
+                        let xpath = self.ui_tree.get_xpath_for_element(active_ui_element, self.simple_xpath);
+                        ctx.copy_text(xpath);
+                        self.set_status("XPath copied to clipboard".to_string(), Duration::seconds(2));
+                    }
+                    None => {
+                        self.set_status("No active element, nothing to copy".to_string(), Duration::seconds(2));
+                    }
+                }
+            }
+            Action::SwitchDisplayMode => {
+                self.display_mode = match self.display_mode {
+                    DisplayMode::Explore => DisplayMode::XpathTest,
+                    DisplayMode::XpathTest => DisplayMode::Search,
+                    DisplayMode::Search => DisplayMode::Explore,
+                };
+            }
+            Action::EnterHintMode => {
+                self.enter_hint_mode();
+            }
+            Action::ToggleViNav => {
+                self.vi_nav = !self.vi_nav;
+                if self.vi_nav {
+                    self.set_status("Vi navigation enabled (h/j/k/l, g/End, n/p)".to_string(), Duration::seconds(4));
+                } else {
+                    self.set_status("Vi navigation disabled".to_string(), Duration::seconds(2));
+                }
+            }
+        }
+    }
+
+    /// Label every currently visible element with a short typeable hint, nearest the
+    /// cursor first, so it can be selected without the mouse.
+    fn enter_hint_mode(&mut self) {
+        let screen_width = self.app_context.screen_width as i32;
+        let screen_height = self.app_context.screen_height as i32;
+
+        let candidates: Vec<(usize, RECT)> = self.ui_tree.get_elements().iter()
+            .map(|ui_element| {
+                let bounding_rect = ui_element.get_element_props().get_element().get_bounding_rectangle();
+                let scale = crate::monitors::scale_for_rect(&bounding_rect);
+                let rect = RECT {
+                    left: (bounding_rect.get_left() as f32 * scale) as i32,
+                    top: (bounding_rect.get_top() as f32 * scale) as i32,
+                    right: (bounding_rect.get_right() as f32 * scale) as i32,
+                    bottom: (bounding_rect.get_bottom() as f32 * scale) as i32,
+                };
+                (ui_element.get_tree_index(), rect)
+            })
+            .filter(|(_, rect)| {
+                rect.right > rect.left && rect.bottom > rect.top
+                    && rect.right > 0 && rect.bottom > 0
+                    && rect.left < screen_width && rect.top < screen_height
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            self.set_status("No visible elements to hint".to_string(), Duration::seconds(2));
+            return;
+        }
+
+        let cursor_position = unsafe {
+            let mut cursor_pos = POINT::default();
+            let _ = GetCursorPos(&mut cursor_pos);
+            cursor_pos
+        };
+
+        let count = candidates.len();
+        self.hints.enter(candidates, cursor_position);
+        self.set_status(format!("Hint mode: type a label to jump ({count} elements)"), Duration::seconds(4));
+    }
+
+    /// Move the active element by one `ViMotion` step, scanning the flat element vec for
+    /// the destination and updating `state` exactly as a mouse hover or hint jump would -
+    /// `render_ui_element_details_screen`'s existing `overlay.update` call then redraws
+    /// the highlight frame from `state.active_ui_element` on the next frame.
+    fn apply_vi_motion(&mut self, motion: crate::vi_motion::ViMotion, state: &mut TreeState) {
+        let elements = self.ui_tree.get_elements();
+        if elements.is_empty() {
+            self.set_status("No elements to navigate".to_string(), Duration::seconds(2));
+            return;
+        }
+
+        let current = state.active_ui_element
+            .and_then(|tree_index| elements.iter().position(|e| e.get_tree_index() == tree_index))
+            .unwrap_or(0);
+
+        match crate::vi_motion::apply(elements, current, motion) {
+            Some(next) => {
+                let element = &elements[next];
+                let tree_index = element.get_tree_index();
+                let props = element.get_element_props().clone();
+                state.update_state(props, tree_index);
+            }
+            None => {
+                self.set_status("No element in that direction".to_string(), Duration::seconds(2));
+            }
+        }
+    }
+
+    /// Execute a command that arrived over the IPC socket, against `&mut self`/`state`.
+    fn handle_ipc_command(&mut self, command: crate::ipc::IpcCommand, state: &mut TreeState) -> crate::ipc::IpcResponse {
+        use crate::ipc::{IpcCommand, IpcResponse};
+        use serde_json::json;
+
+        match command {
+            IpcCommand::RefreshTree => {
+                self.app_mode = AppMode::NeedsTreeRefresh;
+                IpcResponse::ok(json!({ "status": "refreshing" }))
+            }
+            IpcCommand::GetActiveElement => {
+                match state.active_ui_element {
+                    Some(index) => {
+                        let (_, element) = self.ui_tree.node(index);
+                        let xpath = self.ui_tree.get_xpath_for_element(index, self.simple_xpath);
+                        IpcResponse::ok(json!({
+                            "name": element.get_name(),
+                            "control_type": element.get_localized_control_type(),
+                            "classname": element.get_classname(),
+                            "automation_id": element.get_automation_id(),
+                            "xpath": xpath,
+                        }))
+                    }
+                    None => IpcResponse::err("no active element"),
+                }
+            }
+            IpcCommand::EvalXPath { expression } => {
+                let srcxml = self.ui_tree.get_xml_dom_tree().to_owned();
+                let eval_result = xpath_eval::eval_xpath(expression, srcxml);
+                if eval_result.is_success() {
+                    let items: Vec<String> = eval_result.get_result_items().iter().map(|item| item.get_item_xml()).collect();
+                    IpcResponse::ok(json!({ "count": eval_result.get_result_count(), "items": items }))
+                } else {
+                    IpcResponse::err(eval_result.get_error_msg())
+                }
+            }
+            IpcCommand::SetActiveElement { runtime_id } => {
+                match self.ui_tree.get_tree().get_element_by_runtime_id(runtime_id) {
+                    Some(node) => {
+                        let index = node.index;
+                        let data = node.data.clone();
+                        state.update_state(data, index);
+                        IpcResponse::ok(json!({ "tree_index": index }))
+                    }
+                    None => IpcResponse::err("no element with that runtime id"),
+                }
+            }
+        }
+    }
+
 
     fn set_status(&mut self, msg: String, duration: Duration) {
         let status_msg = AppStatusMsg::new_with_duration(msg, duration);
@@ -896,6 +1221,14 @@ impl eframe::App for UIExplorer {
             // println!("Path to active ui element {:?} set to : {:?}", state.active_ui_element,  state.path_to_active_ui_element);
         }
 
+        // service any IPC commands that arrived since the last frame - handled here so
+        // every tree mutation they trigger stays on the UI thread, the same way tree
+        // refreshes already do via the `Sender/Receiver<UITreeXML>` pair
+        while let Ok(request) = self.ipc_rx.try_recv() {
+            let response = self.handle_ipc_command(request.command, &mut state);
+            let _ = request.reply_tx.send(response);
+        }
+
         // manage the AppStatusMsg lifecycle
         if let Some(status_msg) = &self.status_msg {
             if status_msg.is_expired() {
@@ -985,6 +1318,17 @@ impl eframe::App for UIExplorer {
                 // Xpath testing screen
                 self.render_xpath_screen(ctx);
             }
+            DisplayMode::Search => {
+                // Regex search screen, plus the element details/highlight panel so
+                // navigating between matches behaves exactly like the Explore view
+                self.render_search_screen(ctx, &mut state);
+                self.render_ui_element_details_screen(ctx, &mut state);
+            }
+        }
+
+        // redraw hint labels on top of everything else while hint mode is active
+        if self.hints.is_active() {
+            self.hints.draw();
         }
 
         // finally update the state
@@ -997,6 +1341,36 @@ impl eframe::App for UIExplorer {
 }
 
 
+/// The single lowercase letter a hint-mode keystroke contributes, if any - hint labels
+/// are drawn from a lowercase alphabet, so only single-letter keys are meaningful here.
+fn hint_char(key: egui::Key) -> Option<char> {
+    let name = key.name();
+    if name.chars().count() == 1 {
+        name.chars().next().map(|c| c.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+/// The `ViMotion` a vi-navigation keystroke maps to, if any - vim-like `hjkl` for
+/// parent/sibling/child, `g`/`End` for the first/last element, `n`/`p` to step between
+/// z-order bands (e.g. between separate top-level windows).
+fn vi_motion_for_key(key: egui::Key) -> Option<crate::vi_motion::ViMotion> {
+    use crate::vi_motion::ViMotion;
+
+    match key {
+        egui::Key::H => Some(ViMotion::Parent),
+        egui::Key::L => Some(ViMotion::FirstChild),
+        egui::Key::J => Some(ViMotion::NextSibling),
+        egui::Key::K => Some(ViMotion::PrevSibling),
+        egui::Key::G => Some(ViMotion::First),
+        egui::Key::End => Some(ViMotion::Last),
+        egui::Key::N => Some(ViMotion::NextZOrder),
+        egui::Key::P => Some(ViMotion::PrevZOrder),
+        _ => None,
+    }
+}
+
 fn event_summary(event: &egui::Event, ui_elements: &Vec<UIElementInTreeXML>) -> String {
     match event {
         egui::Event::PointerMoved { .. }   => {        
@@ -1009,7 +1383,7 @@ fn event_summary(event: &egui::Event, ui_elements: &Vec<UIElementInTreeXML>) ->
                 cursor_pos
             };
 
-            if let Some(ui_element_props) = rectangle::get_point_bounding_rect(&cursor_position, ui_elements) {
+            if let Some((_, ui_element_props)) = rectangle::get_point_bounding_rect(&cursor_position, ui_elements) {
                 // format!("MouseMoved {{ x: {}, y: {} }} over {}", cursor_position.x, cursor_position.y, ui_element_props.name)
                 let ui_element_props = ui_element_props.get_element_props();
                 let control_type: String = ui_element_props.get_element().get_control_type().to_string();        
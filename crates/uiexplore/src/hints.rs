@@ -0,0 +1,156 @@
+//! Keyboard-driven "hint mode" for mouse-free element selection, modeled on alacritty's
+//! hint overlay: every visible element gets a short typeable label, and typing narrows
+//! the candidate set by prefix until exactly one element remains.
+use std::cmp::Ordering;
+
+use windows::Win32::Foundation::{POINT, RECT};
+
+use crate::rectangle;
+
+/// The alphabet hint labels are drawn from.
+const DEFAULT_ALPHABET: &str = "asdfghjkl";
+
+/// Assign each of `count` candidates a unique, fixed-length label over `alphabet`, using
+/// the shortest length `L >= 1` for which `alphabet.len().pow(L) >= count` - i.e.
+/// `ceil(log_k(count))` symbols per label, and never fewer than one.
+fn generate_labels(count: usize, alphabet: &str) -> Vec<String> {
+    let symbols: Vec<char> = alphabet.chars().collect();
+    let k = symbols.len().max(1);
+
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let mut length = 1usize;
+    while k.pow(length as u32) < count {
+        length += 1;
+    }
+
+    (0..count)
+        .map(|mut n| {
+            let mut chars = vec![symbols[0]; length];
+            for slot in (0..length).rev() {
+                chars[slot] = symbols[n % k];
+                n /= k;
+            }
+            chars.into_iter().collect()
+        })
+        .collect()
+}
+
+fn distance_to(rect: RECT, point: POINT) -> f64 {
+    let cx = (rect.left + rect.right) as f64 / 2.0;
+    let cy = (rect.top + rect.bottom) as f64 / 2.0;
+    let dx = cx - point.x as f64;
+    let dy = cy - point.y as f64;
+    (dx * dx + dy * dy).sqrt()
+}
+
+pub struct HintCandidate {
+    pub tree_index: usize,
+    pub label: String,
+    pub rect: RECT,
+}
+
+pub enum HintOutcome {
+    /// The typed prefix still matches more than one candidate.
+    Narrowed,
+    /// The typed prefix uniquely identifies this tree index.
+    Matched(usize),
+    /// No candidate matches the typed prefix; the keystroke is ignored.
+    NoMatch,
+}
+
+/// Tracks the active hint overlay, if any - an empty candidate list means hint mode is
+/// inactive.
+#[derive(Default)]
+pub struct HintState {
+    candidates: Vec<HintCandidate>,
+    typed: String,
+    drawn: Vec<RECT>,
+}
+
+impl HintState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.candidates.is_empty()
+    }
+
+    /// Enter hint mode over every `(tree_index, rect)` candidate currently visible on
+    /// screen, ordering label assignment by distance from `cursor` so the elements the
+    /// user is most likely aiming for get the earliest labels in the alphabet.
+    pub fn enter(&mut self, mut candidates: Vec<(usize, RECT)>, cursor: POINT) {
+        candidates.sort_by(|(_, a), (_, b)| {
+            distance_to(*a, cursor)
+                .partial_cmp(&distance_to(*b, cursor))
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let labels = generate_labels(candidates.len(), DEFAULT_ALPHABET);
+
+        self.candidates = candidates
+            .into_iter()
+            .zip(labels)
+            .map(|((tree_index, rect), label)| HintCandidate { tree_index, label, rect })
+            .collect();
+        self.typed.clear();
+    }
+
+    /// Exit hint mode without selecting anything, clearing any labels left on screen.
+    pub fn cancel(&mut self) {
+        self.clear();
+        self.candidates.clear();
+        self.typed.clear();
+    }
+
+    /// Feed one typed character, narrowing the candidate set by prefix.
+    pub fn type_char(&mut self, c: char) -> HintOutcome {
+        let mut typed = self.typed.clone();
+        typed.push(c);
+
+        let matching: Vec<&HintCandidate> = self.candidates.iter().filter(|c| c.label.starts_with(&typed)).collect();
+
+        match matching.len() {
+            0 => HintOutcome::NoMatch,
+            1 => {
+                let tree_index = matching[0].tree_index;
+                self.cancel();
+                HintOutcome::Matched(tree_index)
+            }
+            _ => {
+                self.typed = typed;
+                HintOutcome::Narrowed
+            }
+        }
+    }
+
+    /// The candidates still matching the typed prefix, for rendering hint overlays.
+    pub fn visible_candidates(&self) -> impl Iterator<Item = &HintCandidate> {
+        let typed = self.typed.clone();
+        self.candidates.iter().filter(move |c| c.label.starts_with(&typed))
+    }
+
+    /// Draw (or redraw) the currently visible hint labels, clearing whatever was drawn
+    /// last frame first.
+    pub fn draw(&mut self) {
+        for rect in self.drawn.drain(..) {
+            let _ = rectangle::clear_frame(rect);
+        }
+
+        for candidate in self.visible_candidates() {
+            if rectangle::draw_label(candidate.rect, &candidate.label).is_ok() {
+                self.drawn.push(candidate.rect);
+            }
+        }
+    }
+
+    /// Clear any hint labels left on screen without otherwise touching the candidate set.
+    pub fn clear(&mut self) {
+        for rect in self.drawn.drain(..) {
+            let _ = rectangle::clear_frame(rect);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! A local IPC control socket for scripting a running explorer instance, modeled on
+//! alacritty's IPC daemon: a background thread accepts JSON commands over a named pipe
+//! and hands them to the egui `update` loop through a channel, mirroring the existing
+//! `Sender<_>`/`Receiver<UITreeXML>` pair used for tree refreshes - so every tree
+//! mutation a command triggers still happens on the UI thread.
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::Storage::FileSystem::{ReadFile, WriteFile};
+use windows::Win32::System::Pipes::{
+    ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, PIPE_ACCESS_DUPLEX, PIPE_READMODE_MESSAGE,
+    PIPE_TYPE_MESSAGE, PIPE_WAIT,
+};
+
+/// The pipe name clients connect to; one running explorer instance per desktop session.
+pub const PIPE_NAME: &str = r"\\.\pipe\bromium-uiexplore";
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum IpcCommand {
+    /// Request a tree refresh, the same as clicking the refresh button.
+    RefreshTree,
+    /// Return the currently active element's properties and XPath.
+    GetActiveElement,
+    /// Evaluate an XPath expression against the current tree's XML DOM.
+    EvalXPath { expression: String },
+    /// Move the active element to the node with this runtime id.
+    SetActiveElement { runtime_id: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct IpcResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl IpcResponse {
+    pub fn ok(data: Value) -> Self {
+        Self { success: true, data: Some(data), error: None }
+    }
+
+    pub fn err(msg: impl Into<String>) -> Self {
+        Self { success: false, data: None, error: Some(msg.into()) }
+    }
+}
+
+/// One decoded command plus the channel its response is expected on.
+pub struct IpcRequest {
+    pub command: IpcCommand,
+    pub reply_tx: Sender<IpcResponse>,
+}
+
+/// Start the background listener thread and return the `Receiver` the `update` loop
+/// should drain each frame.
+pub fn spawn_ipc_server(pipe_name: &'static str) -> Receiver<IpcRequest> {
+    let (tx, rx) = channel();
+    thread::spawn(move || ipc_server_loop(pipe_name, tx));
+    rx
+}
+
+fn ipc_server_loop(pipe_name: &str, tx: Sender<IpcRequest>) {
+    loop {
+        match accept_connection(pipe_name) {
+            Ok(handle) => {
+                if let Err(e) = handle_connection(handle, &tx) {
+                    eprintln!("IPC connection error: {e:?}");
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to create IPC pipe: {e:?}");
+                thread::sleep(std::time::Duration::from_secs(1));
+            }
+        }
+    }
+}
+
+fn wide_null(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Create the named pipe and block until a client connects to it.
+fn accept_connection(pipe_name: &str) -> windows::core::Result<HANDLE> {
+    unsafe {
+        let name = wide_null(pipe_name);
+        let handle = CreateNamedPipeW(
+            PCWSTR(name.as_ptr()),
+            PIPE_ACCESS_DUPLEX,
+            PIPE_TYPE_MESSAGE | PIPE_READMODE_MESSAGE | PIPE_WAIT,
+            1,
+            4096,
+            4096,
+            0,
+            None,
+        )?;
+
+        // A client connecting between pipe creation and this call makes
+        // `ConnectNamedPipe` return an error even though that's the success case, so its
+        // result is intentionally ignored - a real failure just surfaces on the
+        // subsequent read/write instead.
+        let _ = ConnectNamedPipe(handle, None);
+
+        Ok(handle)
+    }
+}
+
+fn read_message(handle: HANDLE) -> windows::core::Result<String> {
+    let mut buf = [0u8; 4096];
+    let mut bytes_read: u32 = 0;
+    unsafe {
+        ReadFile(handle, Some(&mut buf), Some(&mut bytes_read), None)?;
+    }
+    Ok(String::from_utf8_lossy(&buf[..bytes_read as usize]).trim().to_string())
+}
+
+fn write_message(handle: HANDLE, text: &str) -> windows::core::Result<()> {
+    let mut bytes_written: u32 = 0;
+    unsafe {
+        WriteFile(handle, Some(text.as_bytes()), Some(&mut bytes_written), None)?;
+    }
+    Ok(())
+}
+
+/// Read one JSON command, hand it to the UI thread via `tx`, wait for the reply, and
+/// write it back - then disconnect, ready for the next client.
+fn handle_connection(handle: HANDLE, tx: &Sender<IpcRequest>) -> windows::core::Result<()> {
+    let request_json = read_message(handle)?;
+
+    let response = match serde_json::from_str::<IpcCommand>(&request_json) {
+        Ok(command) => {
+            let (reply_tx, reply_rx) = channel();
+            if tx.send(IpcRequest { command, reply_tx }).is_err() {
+                IpcResponse::err("explorer is shutting down")
+            } else {
+                reply_rx.recv().unwrap_or_else(|_| IpcResponse::err("no response from explorer"))
+            }
+        }
+        Err(e) => IpcResponse::err(format!("invalid command: {e}")),
+    };
+
+    let response_json = serde_json::to_string(&response).unwrap_or_else(|_| "{\"success\":false}".to_string());
+    write_message(handle, &response_json)?;
+
+    unsafe {
+        let _ = DisconnectNamedPipe(handle);
+        let _ = CloseHandle(handle);
+    }
+
+    Ok(())
+}
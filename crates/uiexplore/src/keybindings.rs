@@ -0,0 +1,96 @@
+//! A remappable key binding layer for the explorer window, modeled on the
+//! trigger/action split used by terminal emulators like alacritty: a binding pairs an
+//! exact key + modifier combination with a semantic `Action`, so `process_event` never
+//! has to special-case individual keys itself.
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    ToggleRecording,
+    RefreshTree,
+    CopyXPath,
+    SwitchDisplayMode,
+    EnterHintMode,
+    ToggleViNav,
+}
+
+/// The modifier mask a binding requires. Matching is exact - a binding requiring only
+/// `ctrl` must not fire when `ctrl+shift` is held - so bindings never shadow each other
+/// through a looser match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Modifiers {
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub shift: bool,
+    #[serde(default)]
+    pub alt: bool,
+}
+
+impl Modifiers {
+    fn matches(&self, pressed: &egui::Modifiers) -> bool {
+        self.ctrl == pressed.ctrl && self.shift == pressed.shift && self.alt == pressed.alt
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyBinding {
+    /// The `egui::Key` variant name, e.g. `"Escape"` or `"F5"`.
+    pub key: String,
+    #[serde(default)]
+    pub modifiers: Modifiers,
+    pub action: Action,
+}
+
+impl KeyBinding {
+    fn new(key: egui::Key, modifiers: Modifiers, action: Action) -> Self {
+        Self { key: format!("{:?}", key), modifiers, action }
+    }
+
+    fn key(&self) -> Option<egui::Key> {
+        egui::Key::from_name(&self.key)
+    }
+}
+
+pub struct KeyBindings {
+    bindings: Vec<KeyBinding>,
+}
+
+impl KeyBindings {
+    pub fn default_bindings() -> Vec<KeyBinding> {
+        vec![
+            KeyBinding::new(egui::Key::Escape, Modifiers::default(), Action::ToggleRecording),
+            KeyBinding::new(egui::Key::F5, Modifiers::default(), Action::RefreshTree),
+            KeyBinding::new(egui::Key::C, Modifiers { ctrl: true, shift: true, alt: false }, Action::CopyXPath),
+            KeyBinding::new(egui::Key::Tab, Modifiers { ctrl: true, shift: false, alt: false }, Action::SwitchDisplayMode),
+            KeyBinding::new(egui::Key::F, Modifiers { ctrl: true, shift: false, alt: false }, Action::EnterHintMode),
+            KeyBinding::new(egui::Key::V, Modifiers { ctrl: true, shift: false, alt: false }, Action::ToggleViNav),
+        ]
+    }
+
+    /// Load bindings from `path`, falling back to [`KeyBindings::default_bindings`] when
+    /// the file is missing or malformed so a bad config never leaves the app unusable.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let bindings = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Vec<KeyBinding>>(&content).ok())
+            .unwrap_or_else(Self::default_bindings);
+        Self { bindings }
+    }
+
+    /// The action bound to `key` pressed with exactly `modifiers`, if any.
+    pub fn action_for(&self, key: egui::Key, modifiers: egui::Modifiers) -> Option<Action> {
+        self.bindings.iter()
+            .find(|binding| binding.key() == Some(key) && binding.modifiers.matches(&modifiers))
+            .map(|binding| binding.action)
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self { bindings: Self::default_bindings() }
+    }
+}
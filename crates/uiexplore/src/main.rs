@@ -3,14 +3,17 @@
 #[macro_use]
 mod macros;
 
-use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN};
-use windows::Win32::Graphics::Gdi::{MONITOR_FROM_FLAGS, MonitorFromPoint};
-use windows::Win32::UI::HiDpi::{DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE, DPI_AWARENESS_PER_MONITOR_AWARE, MONITOR_DPI_TYPE, GetDpiForMonitor, SetProcessDpiAwarenessContext, GetDpiAwarenessContextForProcess, GetAwarenessFromDpiAwarenessContext}; //DPI_AWARENESS, DPI_AWARENESS_CONTEXT, GetThreadDpiAwarenessContext
-use windows::Win32::Foundation::{POINT, HANDLE};
+use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+use windows::Win32::Foundation::POINT;
 
 
 mod rectangle;
 mod commons;
+mod keybindings;
+mod hints;
+mod ipc;
+mod vi_motion;
+mod monitors;
 
 mod app_ui;
 use app_ui::UIExplorer;
@@ -67,12 +70,6 @@ fn main() -> eframe::Result {
     )
 }
 
-#[repr(C)]
-struct ScreenSize {
-    width: i32,
-    height: i32,
-}
-
 #[derive(Debug)]
 #[repr(C)]
 struct AppContext {
@@ -98,66 +95,30 @@ impl AppContext {
         }
     }
 
+    /// Sizes and centers the app window on the monitor under the cursor (falling back to
+    /// `(0, 0)` if the cursor position can't be read) rather than assuming a single
+    /// virtual screen, so the window lands on - and is sized for - whichever display the
+    /// user is actually looking at.
     fn new_from_screen(horizontal_scaling: f32, vertical_scaling: f32) -> Self {
-        
-        let screen_size = get_system_metrics();
-        let screen_width = screen_size.width;
-        let screen_height = screen_size.height; 
-        let screen_scale = get_screen_scale_factor();
+        let (origin_x, origin_y) = cursor_position().unwrap_or((0, 0));
+        let (left, top, right, bottom) = monitors::bounds_for_point(origin_x, origin_y);
+        let screen_width = right - left;
+        let screen_height = bottom - top;
+        let screen_scale = monitors::scale_for_point(origin_x, origin_y);
         let app_width = screen_width as f32 * horizontal_scaling;
         let app_height = screen_height as f32 * vertical_scaling;
-        let app_left = screen_width as f32 / 2.0 - app_width / 2.0;
-        let app_top = screen_height as f32 / 2.0 - app_height / 2.0;
+        let app_left = left as f32 + (screen_width as f32 - app_width) / 2.0;
+        let app_top = top as f32 + (screen_height as f32 - app_height) / 2.0;
         Self::new(screen_width, screen_height, screen_scale, app_width, app_height, app_left, app_top)
     }
 }
 
-fn get_system_metrics() -> ScreenSize {
-    unsafe {
-        let x = GetSystemMetrics(SM_CXSCREEN);
-        let y = GetSystemMetrics(SM_CYSCREEN);
-        // println!("Screen size: {}x{}", x, y);
-        ScreenSize { width: x, height: y }
-    }
-}
-
-fn get_screen_scale_factor() -> f32 {
-
-    unsafe {
-        // First we need to set the DPI awareness context to per monitor aware
-        // This is required to get the correct DPI for the monitor
-        let monitor = MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_FROM_FLAGS { 0: 2 });
-        let _res_dpi_awareness_context = SetProcessDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE);
-        let dpi_awareness_process = GetDpiAwarenessContextForProcess(HANDLE(std::ptr::null_mut()));
-        let awareness_process = GetAwarenessFromDpiAwarenessContext(dpi_awareness_process);
-
-        let awareness_fmt: String;
-        let awareness = match awareness_process {
-            DPI_AWARENESS_PER_MONITOR_AWARE => "Per Monitor Aware",
-            _ => {
-                awareness_fmt = format!("Unknown DPI Awareness: {:?}", awareness_process);
-                awareness_fmt.as_str()
-                },
-        };
-
-        let mut dpi_x = 0;
-        let mut dpi_y = 0;
-        let _res = GetDpiForMonitor(monitor, MONITOR_DPI_TYPE {0: 0}, &mut dpi_x, &mut dpi_y);
-
-
-        // println!("DPI: ({}, {}), Awareness Process: {:?}", dpi_x, dpi_y, awareness);
-
-        let x = GetSystemMetrics(SM_CXSCREEN);
-        let y = GetSystemMetrics(SM_CYSCREEN);
-        let scale_x = dpi_x as f32 / 96.0;
-        let scale_y = dpi_y as f32 / 96.0;
-        let scale = (scale_x + scale_y) / 2.0;
-        println!("Screen size: {}x{}, DPI: {}x{}, Awareness Process: {}, Scale: {}", x, y, dpi_x, dpi_y, awareness, scale);
-
-        scale
-    }
-
-
+/// The current cursor position in virtual-desktop coordinates, or `None` if `GetCursorPos`
+/// fails.
+fn cursor_position() -> Option<(i32, i32)> {
+    let mut point = POINT::default();
+    unsafe { GetCursorPos(&mut point).ok()? };
+    Some((point.x, point.y))
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,212 @@
+//! Per-monitor DPI resolution, replacing `get_screen_scale_factor`'s single-monitor-at-
+//! `(0, 0)` assumption with a full enumeration so a bounding rectangle is scaled by the
+//! DPI of the monitor it actually lives on.
+
+use std::sync::{Mutex, Once};
+use std::thread;
+
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{BOOL, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, MonitorFromPoint, HDC, HMONITOR, MONITORINFO,
+    MONITORINFOF_PRIMARY, MONITOR_FROM_FLAGS,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::HiDpi::{
+    GetDpiForMonitor, SetProcessDpiAwarenessContext, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE,
+    MONITOR_DPI_TYPE,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+    HWND_MESSAGE, MSG, WINDOW_EX_STYLE, WM_DISPLAYCHANGE, WM_SETTINGCHANGE, WNDCLASSW,
+    WS_OVERLAPPED,
+};
+
+use uiautomation::types::Rect;
+
+/// One physical display's bounds and own per-monitor DPI scale - the unit the cached
+/// monitor list is built from, mirroring `src/context.rs`'s `Monitor` pyclass but scoped
+/// to just what `scale_for_rect`/`scale_for_point` need.
+#[derive(Debug, Clone, Copy)]
+struct MonitorInfo {
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+    scale: f32,
+    primary: bool,
+}
+
+impl MonitorInfo {
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.left && x < self.right && y >= self.top && y < self.bottom
+    }
+}
+
+static MONITOR_CACHE: Mutex<Option<Vec<MonitorInfo>>> = Mutex::new(None);
+
+/// Drop the cached monitor list so the next `scale_for_point`/`scale_for_rect` call
+/// re-enumerates the displays - called automatically by the watcher thread started from
+/// `cached_monitors` when a monitor is attached/detached or its resolution/DPI changes,
+/// the way winit invalidates its own cached monitor list.
+pub fn invalidate_monitor_cache() {
+    *MONITOR_CACHE.lock().unwrap() = None;
+}
+
+fn cached_monitors() -> Vec<MonitorInfo> {
+    ensure_display_change_watcher();
+    let mut cache = MONITOR_CACHE.lock().unwrap();
+    if cache.is_none() {
+        *cache = Some(enumerate_monitors());
+    }
+    cache.as_ref().unwrap().clone()
+}
+
+static DISPLAY_CHANGE_WATCHER: Once = Once::new();
+
+/// Start the dedicated message-only-window thread that calls [`invalidate_monitor_cache`]
+/// on `WM_DISPLAYCHANGE`/`WM_SETTINGCHANGE`, the first time the monitor list is needed -
+/// mirroring `winevent-monitor`'s dedicated-thread hook install, just for Win32 window
+/// messages instead of WinEvents. Idempotent: later calls are no-ops.
+fn ensure_display_change_watcher() {
+    DISPLAY_CHANGE_WATCHER.call_once(|| {
+        thread::spawn(|| unsafe {
+            let Some(hwnd) = create_display_change_window() else { return };
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, Some(hwnd), 0, 0).as_bool() {
+                DispatchMessageW(&msg);
+            }
+        });
+    });
+}
+
+fn wide_null(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+unsafe extern "system" fn display_change_wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if msg == WM_DISPLAYCHANGE || msg == WM_SETTINGCHANGE {
+        invalidate_monitor_cache();
+    }
+    unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+}
+
+/// Register the watcher's window class (once per process) and create its hidden,
+/// message-only window, or `None` if either step fails - the watcher thread just exits in
+/// that case, leaving the cache un-invalidated until the next `invalidate_monitor_cache`
+/// caller (same degrade-quietly behavior as the rest of this module's `let _res = ...`
+/// Win32 calls).
+unsafe fn create_display_change_window() -> Option<HWND> {
+    let class_name = wide_null("BromiumUiExploreMonitorWatcher");
+    let instance = unsafe { GetModuleHandleW(None) }.ok()?;
+
+    let wc = WNDCLASSW {
+        lpfnWndProc: Some(display_change_wndproc),
+        hInstance: instance.into(),
+        lpszClassName: PCWSTR(class_name.as_ptr()),
+        ..Default::default()
+    };
+    if unsafe { RegisterClassW(&wc) } == 0 {
+        return None;
+    }
+
+    unsafe {
+        CreateWindowExW(
+            WINDOW_EX_STYLE(0),
+            PCWSTR(class_name.as_ptr()),
+            PCWSTR::null(),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            Some(HWND_MESSAGE),
+            None,
+            Some(instance.into()),
+            None,
+        )
+        .ok()
+    }
+}
+
+unsafe extern "system" fn enum_monitor_proc(hmonitor: HMONITOR, _hdc: HDC, _rect: *mut RECT, lparam: LPARAM) -> BOOL {
+    let monitors = &mut *(lparam.0 as *mut Vec<HMONITOR>);
+    monitors.push(hmonitor);
+    BOOL(1)
+}
+
+/// Every monitor handle currently attached, via `EnumDisplayMonitors`.
+fn enumerate_monitor_handles() -> Vec<HMONITOR> {
+    let mut monitors: Vec<HMONITOR> = Vec::new();
+    unsafe {
+        let _res = EnumDisplayMonitors(HDC(std::ptr::null_mut()), None, Some(enum_monitor_proc), LPARAM(&mut monitors as *mut _ as isize));
+    }
+    monitors
+}
+
+fn monitor_info_from_handle(hmonitor: HMONITOR) -> MonitorInfo {
+    unsafe {
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        let _res = GetMonitorInfoW(hmonitor, &mut info);
+
+        let mut dpi_x = 0;
+        let mut dpi_y = 0;
+        let _res = GetDpiForMonitor(hmonitor, MONITOR_DPI_TYPE { 0: 0 }, &mut dpi_x, &mut dpi_y);
+
+        MonitorInfo {
+            left: info.rcMonitor.left,
+            top: info.rcMonitor.top,
+            right: info.rcMonitor.right,
+            bottom: info.rcMonitor.bottom,
+            scale: dpi_x as f32 / 96.0,
+            primary: (info.dwFlags & MONITORINFOF_PRIMARY) != 0,
+        }
+    }
+}
+
+fn enumerate_monitors() -> Vec<MonitorInfo> {
+    unsafe {
+        // Required once per process to get real per-monitor DPI values out of
+        // `GetDpiForMonitor` instead of a system-wide average.
+        let _res = SetProcessDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE);
+    }
+    let mut monitors: Vec<MonitorInfo> = enumerate_monitor_handles().into_iter().map(monitor_info_from_handle).collect();
+    monitors.sort_by_key(|m| !m.primary);
+    monitors
+}
+
+/// The monitor containing `(x, y)`, falling back to the nearest one (mirroring
+/// `MonitorFromPoint`'s `MONITOR_DEFAULTTONEAREST`) when no cached monitor's bounds
+/// contain it - e.g. right after a display is unplugged but before the cache is
+/// invalidated.
+fn monitor_at(x: i32, y: i32) -> MonitorInfo {
+    let monitors = cached_monitors();
+    if let Some(found) = monitors.iter().find(|m| m.contains(x, y)) {
+        return *found;
+    }
+    unsafe {
+        let hmonitor = MonitorFromPoint(POINT { x, y }, MONITOR_FROM_FLAGS { 0: 2 }); // MONITOR_DEFAULTTONEAREST
+        monitor_info_from_handle(hmonitor)
+    }
+}
+
+/// The DPI scale factor (DPI / 96) of the monitor containing the point `(x, y)`.
+pub fn scale_for_point(x: i32, y: i32) -> f32 {
+    monitor_at(x, y).scale
+}
+
+/// The DPI scale factor of the monitor `rect` lives on, determined from its top-left
+/// corner - the per-monitor-aware replacement for always scaling by the monitor at
+/// `(0, 0)`.
+pub fn scale_for_rect(rect: &Rect) -> f32 {
+    scale_for_point(rect.get_left(), rect.get_top())
+}
+
+/// The bounds, as `(left, top, right, bottom)`, of the monitor containing `(x, y)`.
+pub fn bounds_for_point(x: i32, y: i32) -> (i32, i32, i32, i32) {
+    let monitor = monitor_at(x, y);
+    (monitor.left, monitor.top, monitor.right, monitor.bottom)
+}
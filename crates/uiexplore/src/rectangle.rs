@@ -4,7 +4,7 @@
 use windows::{
     core::{Error, Result},
     Win32::Foundation::{HWND, COLORREF, POINT, RECT},
-    Win32::Graphics::Gdi::{HOLLOW_BRUSH, PS_SOLID, Rectangle, CreatePen, GetStockObject, SelectObject, DeleteObject, GetDC,  ReleaseDC, InvalidateRect},
+    Win32::Graphics::Gdi::{HOLLOW_BRUSH, PS_SOLID, Rectangle, CreatePen, GetStockObject, SelectObject, DeleteObject, GetDC,  ReleaseDC, InvalidateRect, TextOutW, SetBkMode, SetTextColor, TRANSPARENT},
     // Win32::UI::WindowsAndMessaging::*,
 };
 
@@ -12,27 +12,43 @@ use windows::{
 use uitree::UIElementInTreeXML;
 
 
-// TODO: Change the return value to contain both the element and the index
-//       and add the index as an input parameter as well to start looping from that index
-//       as the rectangles are sorted by size
-pub fn get_point_bounding_rect<'a>(point: &'a POINT, ui_elements: &'a Vec<UIElementInTreeXML>) -> Option<&'a UIElementInTreeXML> {
-// pub fn get_point_bounding_rect(point: &Pos2, ui_elements: &Vec<UIElementProps>) -> Option<&UIElementProps> {
-    // printfmt!("Searching for element at point: {{ x: {}, y: {} }} in tree with {} elements.", point.x, point.y, ui_elements.len());
-    // let mut cntr = 0;
-    for element in ui_elements {
-        // cntr += 1;
-        // printfmt!("point: {{ x: {}, y: {} }} searching element: {}", point.x, point.y, cntr);
-        // if cntr == 27 {
-        //     dbg!(element);
-        // }
-        let rect = element.get_element_props().get_element().get_bounding_rectangle();
-        if is_inside_rectancle(&rect, point.x, point.y) {
-            // printfmt!("point: {{ x: {}, y: {} }} searched elements: {} / Found element: {{ name: '{}', control_type: '{}' bounding_rect: {} }}", point.x, point.y, cntr, element.get_element_props().get_element().get_name(), element.get_element_props().get_element().get_control_type(), element.get_element_props().get_element().get_bounding_rectangle());            
-            return Some(element);
+/// Find the topmost element under `point`, returning it together with its index in
+/// `ui_elements` so callers can resume iteration from there.
+///
+/// `ui_elements` is sorted by ascending bounding-rect size within each z-order band, so
+/// a naive first-match picks the smallest *geometric* container rather than the element
+/// that's actually on top. Instead this collects every element whose rectangle contains
+/// the point, then picks the winner by highest `z_order` (the top-level window stacking
+/// order), breaking ties by smallest bounding-rect area so the innermost control within
+/// that window wins.
+pub fn get_point_bounding_rect<'a>(point: &'a POINT, ui_elements: &'a Vec<UIElementInTreeXML>) -> Option<(usize, &'a UIElementInTreeXML)> {
+    let mut best: Option<(usize, &UIElementInTreeXML)> = None;
+
+    for (index, element) in ui_elements.iter().enumerate() {
+        let props = element.get_element_props();
+        let rect = props.get_element().get_bounding_rectangle();
+        if !is_inside_rectancle(&rect, point.x, point.y) {
+            continue;
+        }
+
+        let is_better = match best {
+            None => true,
+            Some((_, current)) => {
+                let current_props = current.get_element_props();
+                match props.get_z_order().cmp(&current_props.get_z_order()) {
+                    std::cmp::Ordering::Greater => true,
+                    std::cmp::Ordering::Equal => props.get_bounding_rect_size() < current_props.get_bounding_rect_size(),
+                    std::cmp::Ordering::Less => false,
+                }
+            }
+        };
+
+        if is_better {
+            best = Some((index, element));
         }
     }
-    // printfmt!("NO ELEMENT FOUND! Searched elements: {}", cntr);
-    None
+
+    best
 }
 
 
@@ -104,6 +120,33 @@ pub fn draw_frame(rect: RECT, outline_width: i32) -> Result<()> {
     }
 }
 
+/// Draw a short text label at the top-left corner of `rect`, the same way hint mode tags
+/// each candidate element. Uses the same "draw straight onto the desktop DC" approach as
+/// `draw_frame`, with a transparent background so the label doesn't blot out the element
+/// underneath it.
+pub fn draw_label(rect: RECT, text: &str) -> Result<()> {
+    unsafe {
+        let hdc = GetDC(Some(HWND(std::ptr::null_mut())));
+        if hdc.is_invalid() {
+            return Err(Error::from_win32());
+        }
+
+        let color = COLORREF(393004);
+        SetTextColor(hdc, color);
+        SetBkMode(hdc, TRANSPARENT);
+
+        let wide: Vec<u16> = text.encode_utf16().collect();
+        let ok = TextOutW(hdc, rect.left, rect.top, &wide);
+
+        ReleaseDC(Some(HWND(std::ptr::null_mut())), hdc);
+
+        if !ok.as_bool() {
+            return Err(Error::from_win32());
+        }
+        Ok(())
+    }
+}
+
 pub fn clear_frame(rect: RECT) -> Result<()> {
     unsafe {
         // Force redraw of the region
@@ -111,3 +154,64 @@ pub fn clear_frame(rect: RECT) -> Result<()> {
         Ok(())
     }
 }
+
+/// The smallest rectangle that contains both `a` and `b`.
+fn union_rect(a: RECT, b: RECT) -> RECT {
+    RECT {
+        left: a.left.min(b.left),
+        top: a.top.min(b.top),
+        right: a.right.max(b.right),
+        bottom: a.bottom.max(b.bottom),
+    }
+}
+
+/// Remembers the last-drawn highlight rectangle so repeated updates to the same
+/// target don't repaint, and only the changed screen region gets invalidated when it
+/// does move.
+///
+/// Without this, redrawing the overlay on every mouse move (even onto the same
+/// element) invalidates and repaints the full target rect each time, which is the
+/// hover flicker this is meant to eliminate.
+#[derive(Default)]
+pub struct OverlayState {
+    last_rect: Option<RECT>,
+}
+
+impl OverlayState {
+    pub fn new() -> Self {
+        Self { last_rect: None }
+    }
+
+    /// Move the highlight to `rect`, drawing only if it actually changed.
+    ///
+    /// When it differs from the last-drawn rect, only the union of the old and new
+    /// rects is invalidated before redrawing, instead of the whole target area.
+    pub fn update(&mut self, rect: RECT, outline_width: i32) -> Result<()> {
+        if self.last_rect == Some(rect) {
+            // Nothing moved - skip the repaint entirely.
+            return Ok(());
+        }
+
+        if let Some(prev_rect) = self.last_rect {
+            clear_frame(union_rect(prev_rect, rect))?;
+        }
+        draw_frame(rect, outline_width)?;
+
+        self.last_rect = Some(rect);
+        Ok(())
+    }
+
+    /// Stop tracking a highlight and clear whatever was last drawn.
+    pub fn clear(&mut self) -> Result<()> {
+        if let Some(prev_rect) = self.last_rect.take() {
+            clear_frame(prev_rect)?;
+        }
+        Ok(())
+    }
+
+    /// Forget the last-drawn rect without clearing the screen, for callers that just
+    /// invalidated the region themselves (e.g. a full-screen redraw).
+    pub fn reset(&mut self) {
+        self.last_rect = None;
+    }
+}
@@ -0,0 +1,81 @@
+//! Keyboard-only tree navigation modeled on alacritty's `ViMotion`: each motion resolves
+//! to a neighboring element so `TreeState::active_ui_element` can move without the mouse.
+//!
+//! Motions are implemented as scans over the flat `Vec<UIElementInTreeXML>` using each
+//! element's recorded `level` and `z_order`, rather than walking `UITreeMap`'s internal
+//! parent/child indices directly - elements are stored in depth-first order, so a node's
+//! parent, children and same-level siblings can all be found by scanning forward or
+//! backward for the next element whose `level` matches.
+use uitree::UIElementInTreeXML;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViMotion {
+    /// The nearest enclosing element - one level up.
+    Parent,
+    /// The first child of the current element, if it has one.
+    FirstChild,
+    /// The next element at the same level within the same parent.
+    NextSibling,
+    /// The previous element at the same level within the same parent.
+    PrevSibling,
+    /// The very first element in the tree.
+    First,
+    /// The very last element in the tree.
+    Last,
+    /// The next element belonging to a higher top-level z-order band.
+    NextZOrder,
+    /// The previous element belonging to a lower top-level z-order band.
+    PrevZOrder,
+}
+
+/// The vec index of the motion's destination from `current`, if any.
+pub fn apply(elements: &[UIElementInTreeXML], current: usize, motion: ViMotion) -> Option<usize> {
+    if elements.is_empty() {
+        return None;
+    }
+
+    // `elements` may be sorted for hit-testing; tree indices are assigned depth-first, so
+    // walk the vec in tree-index order and map the result back to a vec index.
+    let mut order: Vec<usize> = (0..elements.len()).collect();
+    order.sort_by_key(|&i| elements[i].get_tree_index());
+    let current = order.iter().position(|&i| i == current)?;
+
+    let level = |i: usize| elements[order[i]].get_element_props().get_level();
+    let z_order = |i: usize| elements[order[i]].get_element_props().get_z_order();
+
+    let target = match motion {
+        ViMotion::Parent => {
+            let target_level = level(current).checked_sub(1)?;
+            (0..current).rev().find(|&i| level(i) == target_level)
+        }
+        ViMotion::FirstChild => {
+            let child_level = level(current) + 1;
+            let next = current + 1;
+            (next < elements.len() && level(next) == child_level).then_some(next)
+        }
+        ViMotion::NextSibling => {
+            let same_level = level(current);
+            ((current + 1)..elements.len())
+                .take_while(|&i| level(i) >= same_level)
+                .find(|&i| level(i) == same_level)
+        }
+        ViMotion::PrevSibling => {
+            let same_level = level(current);
+            (0..current)
+                .rev()
+                .take_while(|&i| level(i) >= same_level)
+                .find(|&i| level(i) == same_level)
+        }
+        ViMotion::First => Some(0),
+        ViMotion::Last => Some(elements.len() - 1),
+        ViMotion::NextZOrder => {
+            let current_z = z_order(current);
+            ((current + 1)..elements.len()).find(|&i| z_order(i) > current_z)
+        }
+        ViMotion::PrevZOrder => {
+            let current_z = z_order(current);
+            (0..current).rev().find(|&i| z_order(i) < current_z)
+        }
+    };
+    target.map(|i| order[i])
+}
@@ -0,0 +1,78 @@
+//! A folding layer over [`UITree`] for interactive, collapsible navigation.
+use crate::save_ui_element::SaveUIElement;
+use crate::uiexplore_xml::UITree;
+use crate::UIHashSet;
+
+/// The key a node is tracked under: its runtime id, joined the same way
+/// [`crate::uiexplore_xml`] keys the underlying `UITreeMap`.
+fn runtime_id_key(data: &SaveUIElement) -> String {
+    data.get_runtime_id().iter().map(|x| x.to_string()).collect::<Vec<String>>().join("-")
+}
+
+/// Tracks which nodes of a `UITree` are folded (collapsed), keyed by runtime id rather
+/// than tree index so the fold state survives a rebuild of the tree - re-scanning the
+/// desktop assigns fresh indices, but the runtime ids (and thus what the user had
+/// expanded) stay put.
+#[derive(Debug, Default)]
+pub struct FoldState {
+    collapsed: UIHashSet<String>,
+}
+
+impl FoldState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Flip the collapsed state of `index`.
+    pub fn toggle(&mut self, tree: &UITree, index: usize) {
+        let key = runtime_id_key(tree.node(index).1);
+        if !self.collapsed.remove(&key) {
+            self.collapsed.insert(key);
+        }
+    }
+
+    /// Collapse every node in the tree.
+    pub fn collapse_all(&mut self, tree: &UITree) {
+        tree.for_each(|_, data| {
+            self.collapsed.insert(runtime_id_key(data));
+        });
+    }
+
+    /// Unfold every ancestor of `index` (but not `index` itself) so a searched-for
+    /// element becomes visible.
+    pub fn expand_to(&mut self, tree: &UITree, index: usize) {
+        for ancestor in tree.get_tree().get_path_to_element(index) {
+            if ancestor == index {
+                continue;
+            }
+            self.collapsed.remove(&runtime_id_key(tree.node(ancestor).1));
+        }
+        // `get_path_to_element` leaves out the root, which is an ancestor of every other node.
+        if index != tree.root() {
+            self.collapsed.remove(&runtime_id_key(tree.node(tree.root()).1));
+        }
+    }
+
+    /// The flattened, depth-annotated sequence of currently-visible nodes, honoring
+    /// collapsed ancestors - a ready-made model for rendering an indented tree view
+    /// without reimplementing traversal and visibility bookkeeping on top of the raw
+    /// `children`/`node` accessors.
+    pub fn visible_nodes<'a>(&self, tree: &'a UITree) -> Vec<(usize, usize, &'a SaveUIElement)> {
+        let mut out = Vec::new();
+        self.visit(tree, tree.root(), 0, &mut out);
+        out
+    }
+
+    fn visit<'a>(&self, tree: &'a UITree, index: usize, depth: usize, out: &mut Vec<(usize, usize, &'a SaveUIElement)>) {
+        let (_, data) = tree.node(index);
+        out.push((index, depth, data));
+
+        if self.collapsed.contains(&runtime_id_key(data)) {
+            return;
+        }
+
+        for &child in tree.children(index) {
+            self.visit(tree, child, depth + 1, out);
+        }
+    }
+}
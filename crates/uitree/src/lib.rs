@@ -5,7 +5,24 @@ type UIHashSet<T, S = std::hash::RandomState> = std::collections::HashSet<T, S>;
 mod macros;
 
 mod tree_map;
-use tree_map::UITreeMap;
+pub use tree_map::{UITreeMap, UITreeNode};
+
+mod conversion;
+
+mod save_ui_element;
+pub use save_ui_element::SaveUIElement as SaveUIElementXML;
+
+mod uiexplore_xml;
+pub use uiexplore_xml::{UITree as UITreeXML, UIElementInTree as UIElementInTreeXML, get_all_elements_xml};
+
+mod xpath_gen;
+pub use xpath_gen::generate_xpath as generate_element_xpath;
+
+mod fold;
+pub use fold::FoldState;
+
+mod semantic;
+pub use semantic::{Embedder, SemanticIndex};
 
 mod uiexplore;
 pub use uiexplore::{UITree, UIElementInTree, get_all_elements};
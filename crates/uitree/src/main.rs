@@ -2,6 +2,7 @@
 mod macros;
 
 use chrono::Utc;
+use serde::Serialize;
 use std::fs::{File, OpenOptions};
 use std::io::{BufWriter, Write};
 // use std::path::PathBuf;
@@ -13,18 +14,37 @@ use std::sync::mpsc::{Receiver, Sender};
 use uitree::{UITree, get_all_elements};
 use uitree::{UITreeIter, get_all_elements_iterative};
 
+/// Whether a [`FileWriter`] emits one pretty-printed JSON document (a full tree
+/// snapshot, written once) or newline-delimited JSON (a stream of events, one compact
+/// object per `write` call) - the two shapes downstream tooling actually wants instead
+/// of the unparseable `{:#?}` debug dump this used to produce.
+enum OutputFormat {
+    PrettyJson,
+    Jsonl,
+}
+
+impl OutputFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::PrettyJson => "json",
+            OutputFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
 struct FileWriter {
     // outfile_name: PathBuf,
+    format: OutputFormat,
     outfile_writer: BufWriter<File>,
 }
 
 impl FileWriter {
-    fn new(outfile_prefix: &str) -> Self {
-        
+    fn new(outfile_prefix: &str, format: OutputFormat) -> Self {
+
         let tmstmp = Utc::now().format("%Y%m%d%H%M%S").to_string();
-        let filename = format!("uitree_{}_{}.txt", outfile_prefix, tmstmp);
+        let filename = format!("uitree_{}_{}.{}", outfile_prefix, tmstmp, format.extension());
         // let mut outfile_name = PathBuf::new();
-                
+
         let err_msg = format!("Unable to create file: {}", filename);
 
         let f = OpenOptions::new()
@@ -34,40 +54,55 @@ impl FileWriter {
             .expect(&err_msg);
         let outfile_writer = BufWriter::new(f);
 
-        FileWriter { outfile_writer }
+        FileWriter { format, outfile_writer }
     }
 
-    fn write(&mut self, content: &str) {
-        self.outfile_writer.write_all(content.as_bytes())
+    /// Serialize `value` per `self.format` and append it to the file - a JSONL writer
+    /// adds the trailing newline a caller would otherwise have to remember itself.
+    fn write<T: Serialize>(&mut self, value: &T) {
+        let serialized = match self.format {
+            OutputFormat::PrettyJson => serde_json::to_string_pretty(value),
+            OutputFormat::Jsonl => serde_json::to_string(value),
+        };
+
+        let mut serialized = match serialized {
+            Ok(serialized) => serialized,
+            Err(e) => {
+                printfmt!("Failed to serialize value for {:?}: {}", self.format.extension(), e);
+                return;
+            }
+        };
+
+        if matches!(self.format, OutputFormat::Jsonl) {
+            serialized.push('\n');
+        }
+
+        self.outfile_writer.write_all(serialized.as_bytes())
             .expect("Unable to write to file");
     }
-    
+
 }
 
 fn main() {
 
-    // create file writers
-    let mut file_writer_recursive = FileWriter::new("recursive_uitree");
-    let mut file_writer_iterative = FileWriter::new("iterative_uitree");
+    // create file writers - a full tree snapshot is one pretty JSON document
+    let mut file_writer_recursive = FileWriter::new("recursive_uitree", OutputFormat::PrettyJson);
+    let mut file_writer_iterative = FileWriter::new("iterative_uitree", OutputFormat::PrettyJson);
 
     // recursive
-    
+
     let (tx, rx): (Sender<_>, Receiver<UITree>) = channel();
     printfmt!("Spawning separate thread to get ui tree");
     thread::spawn(|| {
         get_all_elements(tx, None);
     });
     printfmt!("Spawned separate thread to get ui tree");
-    
+
     let ui_tree: UITree = rx.recv().unwrap();
     printfmt!("done getting ui tree");
     printfmt!("No of elemetns in UI Tree: {:#}", ui_tree.get_elements().len());
-    
-    ui_tree.for_each(|_index, element| {
-        // printfmt!("Element: {:#?}", element);
-        // write to file
-        file_writer_recursive.write(&format!("{:#?}\n", element));
-    });
+
+    file_writer_recursive.write(ui_tree.get_elements());
 
 
     // iterative
@@ -77,16 +112,12 @@ fn main() {
         get_all_elements_iterative(tx_iter, None);
     });
     printfmt!("Spawned separate thread to get ui tree iteratively");
-    
+
     let ui_tree_iter: UITreeIter = rx_iter.recv().unwrap();
     printfmt!("done getting ui tree iteratively");
     printfmt!("No of elemetns in UI Tree Iter: {:#}", ui_tree_iter.get_elements().len());
-    
-    ui_tree_iter.for_each(|_index, element| {
-        // printfmt!("Element: {:#?}", element);
-        // write to file
-        file_writer_iterative.write(&format!("{:#?}\n", element));
-    });
-    
+
+    file_writer_iterative.write(ui_tree_iter.get_elements());
+
 }
 
@@ -1,10 +1,12 @@
-// use std::sync::Mutex;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
 
 use uiautomation::{UIAutomation, UIElement};
 use uiautomation::types::Handle;
 use log::{debug, info, warn, error};
+use tracing::instrument;
 
-#[derive(Debug, Clone)]
 pub struct SaveUIElement {
     name: String,
     classname: String,
@@ -19,7 +21,82 @@ pub struct SaveUIElement {
     level: usize,
     z_order: usize,
     xpath: Option<String>,
-    // element: Mutex<Option<&'a UIElement>>,
+    /// Memoized result of resolving this node's `runtime_id` back to a live `UIElement`,
+    /// filled in lazily by `get_live_element` the first time a caller needs it rather
+    /// than on every `SaveUIElement` construction.
+    cached_element: Mutex<Option<UIElement>>,
+}
+
+impl serde::Serialize for SaveUIElement {
+    /// Written by hand rather than derived: `cached_element` holds a live COM
+    /// `UIElement` handle that isn't (and shouldn't be) serializable, and the bounding
+    /// rectangle is flattened to a plain `[left, top, right, bottom]` array since
+    /// `uiautomation::types::Rect` doesn't implement `Serialize` either.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("SaveUIElement", 13)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("classname", &self.classname)?;
+        state.serialize_field("control_type", &self.control_type)?;
+        state.serialize_field("localized_control_type", &self.localized_control_type)?;
+        state.serialize_field("framework_id", &self.framework_id)?;
+        state.serialize_field("runtime_id", &self.runtime_id)?;
+        state.serialize_field("automation_id", &self.automation_id)?;
+        state.serialize_field("handle", &self.handle)?;
+        state.serialize_field("bounding_rect", &[
+            self.bounding_rect.get_left(),
+            self.bounding_rect.get_top(),
+            self.bounding_rect.get_right(),
+            self.bounding_rect.get_bottom(),
+        ])?;
+        state.serialize_field("bounding_rect_size", &self.bounding_rect_size)?;
+        state.serialize_field("level", &self.level)?;
+        state.serialize_field("z_order", &self.z_order)?;
+        state.serialize_field("xpath", &self.xpath)?;
+        state.end()
+    }
+}
+
+impl std::fmt::Debug for SaveUIElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SaveUIElement")
+            .field("name", &self.name)
+            .field("classname", &self.classname)
+            .field("control_type", &self.control_type)
+            .field("automation_id", &self.automation_id)
+            .field("runtime_id", &self.runtime_id)
+            .field("level", &self.level)
+            .field("z_order", &self.z_order)
+            .field("xpath", &self.xpath)
+            .finish()
+    }
+}
+
+impl Clone for SaveUIElement {
+    fn clone(&self) -> Self {
+        SaveUIElement {
+            name: self.name.clone(),
+            classname: self.classname.clone(),
+            control_type: self.control_type.clone(),
+            localized_control_type: self.localized_control_type.clone(),
+            framework_id: self.framework_id.clone(),
+            runtime_id: self.runtime_id.clone(),
+            automation_id: self.automation_id.clone(),
+            handle: self.handle,
+            bounding_rect: self.bounding_rect.clone(),
+            bounding_rect_size: self.bounding_rect_size,
+            level: self.level,
+            z_order: self.z_order,
+            xpath: self.xpath.clone(),
+            // A clone starts without a memoized element rather than locking `self` to
+            // copy one out - cheap to re-resolve lazily if the clone ever needs it.
+            cached_element: Mutex::new(None),
+        }
+    }
 }
 
 impl SaveUIElement {
@@ -80,20 +157,35 @@ impl SaveUIElement {
         self
     }
 
-    // pub fn get_element(&self) -> &UIElement {
-    //     if let Some(el) = &self.element {
-    //         return el;
-    //     } else {
-    //         let el = get_ui_element_by_runtimeid(self.runtime_id.clone()).expect("Failed to find element by runtime id");
-    //         self.element.insert(el.clone());
-    //         return &self.element.unwrap();
-    //     }
-    // }
+    /// Resolve this node's `runtime_id` back to a live `UIElement`, memoizing the result
+    /// so repeated calls don't re-walk the tree. Searches from `root` (the desktop root
+    /// when `None`) down to `depth` levels - a caller that already knows the containing
+    /// window should pass it in to avoid the depth-99 global scan.
+    pub fn get_live_element(&self, root: Option<UIElement>, depth: u32) -> Option<UIElement> {
+        if let Ok(cached) = self.cached_element.lock() {
+            if let Some(element) = cached.as_ref() {
+                return Some(element.clone());
+            }
+        }
+
+        let resolved = get_ui_element_by_runtimeid_in(self.runtime_id.clone(), root, depth)?;
+        if let Ok(mut cached) = self.cached_element.lock() {
+            *cached = Some(resolved.clone());
+        }
+        Some(resolved)
+    }
 
     pub fn set_xpath(&mut self, xpath: String) {
         self.xpath = Some(xpath)
     }
 
+    /// Refresh just this node's geometry in place - for a caller (e.g. a live tree
+    /// applying an `ObjectLocationChange` event) that already knows the element moved
+    /// and only needs the bounding rectangle recomputed, not the rest of `SaveUIElement`.
+    pub fn set_bounding_rectangle(&mut self, bounding_rect: uiautomation::types::Rect) {
+        self.bounding_rect_size = (bounding_rect.get_right() - bounding_rect.get_left()) * (bounding_rect.get_bottom() - bounding_rect.get_top());
+        self.bounding_rect = bounding_rect;
+    }
 
 }
 
@@ -115,8 +207,7 @@ impl From<UIElement> for SaveUIElement {
         let handle : isize = item.get_native_window_handle().unwrap_or(Handle::from(0 as isize)).into();
         let bounding_rect: uiautomation::types::Rect = item.get_bounding_rectangle().unwrap_or(uiautomation::types::Rect::new(0, 0, 0, 0));
         let bounding_rect_size: i32 = (bounding_rect.get_right() - bounding_rect.get_left()) * (bounding_rect.get_bottom() - bounding_rect.get_top());
-        // let element = Mutex::new(None);
-        
+
         SaveUIElement {
             name,
             classname,
@@ -131,10 +222,72 @@ impl From<UIElement> for SaveUIElement {
             level: 0,
             z_order: 0,
             xpath: None,
-            // element,
+            cached_element: Mutex::new(None),
         }
     }
 }
+
+type UiaJob = Box<dyn FnOnce(&UIAutomation) + Send>;
+
+/// Sender into the single long-lived worker thread that owns the process's cached
+/// `UIAutomation` instance. Spawned lazily on first use and kept alive for the rest of
+/// the process, so the instance it caches actually survives across calls instead of
+/// dying with a short-lived per-call thread the way a `thread_local!` paired with
+/// `thread::spawn` would.
+static UIA_WORKER: std::sync::OnceLock<mpsc::Sender<UiaJob>> = std::sync::OnceLock::new();
+
+fn uia_worker() -> &'static mpsc::Sender<UiaJob> {
+    UIA_WORKER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<UiaJob>();
+        thread::spawn(move || {
+            let uia = get_ui_automation_instance();
+            for job in rx {
+                if let Some(uia) = uia.as_ref() {
+                    // Isolated so a single bad UIA call can't take the worker thread down
+                    // with it - without this, the thread dying would drop its `Receiver`
+                    // and every later `execute_with_timeout` call would silently get `None`
+                    // forever, with no way to tell a timeout apart from a dead worker.
+                    if let Err(e) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| job(uia))) {
+                        warn!("UIA worker job panicked, worker thread staying alive: {}", panic_message(&e));
+                    }
+                }
+            }
+        });
+        tx
+    })
+}
+
+/// Best-effort text for a `catch_unwind` payload - `Box<dyn Any + Send>` doesn't implement
+/// `Debug`, so this pulls out the message for the common `panic!("...")`/`&str`/`String`
+/// cases and falls back to a generic label for anything else.
+fn panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+/// Hand `f` to the worker thread to run against its cached `UIAutomation` instance, and
+/// wait up to `timeout_ms` for it to finish, so a blocking UIA call that doesn't support
+/// cancellation can't hang its caller forever. `f` runs on the worker thread rather than
+/// a fresh one per call, so the cached instance it's given is reused across calls
+/// instead of being recreated (and immediately discarded) every time.
+fn execute_with_timeout<T, F>(timeout_ms: u64, f: F) -> Option<T>
+where
+    F: FnOnce(&UIAutomation) -> T,
+    F: Send + 'static,
+    T: Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    uia_worker().send(Box::new(move |uia: &UIAutomation| {
+        let _ = tx.send(f(uia));
+    })).ok()?;
+    rx.recv_timeout(Duration::from_millis(timeout_ms)).ok()
+}
+
 #[allow(dead_code)]
 fn get_ui_automation_instance() -> Option<UIAutomation> {
     debug!("Creating UIAutomation instance");
@@ -180,22 +333,42 @@ impl uiautomation::filters::MatcherFilter for RuntimeIdFilter {
 }
 
 #[allow(dead_code)]
+#[instrument(fields(runtime_id = ?runtime_id))]
 pub fn get_ui_element_by_runtimeid(runtime_id: Vec<i32>) -> Option<UIElement> {
+    get_ui_element_by_runtimeid_in(runtime_id, None, 99)
+}
+
+/// As [`get_ui_element_by_runtimeid`], but searches from `root` (the desktop root when
+/// `None`) down to at most `depth` levels instead of always scanning the whole tree -
+/// lets a caller that already knows the containing window skip the depth-99 global
+/// scan. Reuses the worker thread's cached `UIAutomation` instance and bounds the
+/// search with [`execute_with_timeout`] so a hung UIA call can't block the caller
+/// indefinitely.
+#[allow(dead_code)]
+#[instrument(fields(runtime_id = ?runtime_id, depth = depth))]
+pub fn get_ui_element_by_runtimeid_in(runtime_id: Vec<i32>, root: Option<UIElement>, depth: u32) -> Option<UIElement> {
     debug!("Searching for element with runtime id: {:?}", runtime_id);
-    // let automation = UIAutomation::new().unwrap();
-    let uia = get_ui_automation_instance().unwrap();
-    let matcher = uia.create_matcher().timeout(0).filter(Box::new(RuntimeIdFilter(runtime_id))).depth(99);
-    let element = matcher.find_first();
-    
+
+    let element = execute_with_timeout(5_000, move |uia| {
+        let mut matcher = uia.create_matcher().timeout(0).filter(Box::new(RuntimeIdFilter(runtime_id))).depth(depth);
+        if let Some(root) = root {
+            matcher = matcher.from(root);
+        }
+        matcher.find_first()
+    });
+
     match element {
-        Ok(e) => {
+        Some(Ok(e)) => {
             info!("Element found by runtime id: {:?}", e);
             Some(e)
         },
-        Err(e) => {
+        Some(Err(e)) => {
             error!("Error finding element by runtime id: {:?}", e);
             None
+        },
+        None => {
+            error!("Timed out or failed to create a UIAutomation instance while finding element by runtime id");
+            None
         }
     }
-    
 }
@@ -0,0 +1,204 @@
+//! Natural-language element lookup: embeds each node's name, control type, and key
+//! attributes into a fixed-length vector via a pluggable [`Embedder`], caches the
+//! vectors in an on-disk SQLite file keyed by a hash of the tree so re-scanning an
+//! unchanged window is instant, and answers [`SemanticIndex::semantic_find`] queries by
+//! cosine similarity - the vector-search analogue of [`crate::UITreeXML::filter_by_query`]'s
+//! substring search, robust to localization and minor label changes.
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+
+use crate::save_ui_element::SaveUIElement;
+use crate::uiexplore_xml::UITree;
+
+/// Turns a short piece of text into a fixed-length embedding vector. Implement this
+/// against a local model or a remote embedding service; `semantic_find` only relies on
+/// the returned vectors being comparable by cosine similarity, and on every call
+/// returning vectors of [`Embedder::dimension`] length.
+pub trait Embedder {
+    fn embed(&self, text: &str) -> Vec<f32>;
+    fn dimension(&self) -> usize;
+}
+
+/// One indexed node: its position in the tree and the vector computed for its document.
+#[derive(Debug, Clone)]
+struct IndexedRow {
+    node_index: usize,
+    vector: Vec<f32>,
+}
+
+/// An embedding index over a captured `UITree`, backed by an on-disk SQLite cache.
+pub struct SemanticIndex {
+    tree_hash: String,
+    rows: Vec<IndexedRow>,
+}
+
+impl SemanticIndex {
+    /// Build (or load from the cache at `cache_path`) the embedding index for `tree`.
+    ///
+    /// Every node is hashed together into `tree_hash`; if the cache already has rows
+    /// stored under that hash they're reused as-is instead of re-embedding the whole
+    /// tree, so re-scanning the same window twice in a row only pays the embedding cost
+    /// once.
+    pub fn build(tree: &UITree, embedder: &dyn Embedder, cache_path: &Path) -> rusqlite::Result<Self> {
+        let tree_hash = hash_tree(tree);
+        let conn = open_cache(cache_path)?;
+
+        if let Some(rows) = load_cached_rows(&conn, &tree_hash)? {
+            return Ok(SemanticIndex { tree_hash, rows });
+        }
+
+        let mut rows = Vec::new();
+        tree.for_each(|node_index, element| {
+            let document = document_for(element);
+            rows.push(IndexedRow {
+                node_index,
+                vector: embedder.embed(&document),
+            });
+        });
+
+        store_cached_rows(&conn, &tree_hash, &rows)?;
+        Ok(SemanticIndex { tree_hash, rows })
+    }
+
+    /// Embed `query` and return the `top_k` node indices with the highest cosine
+    /// similarity, highest first. Each result can be turned into a selector via
+    /// `get_path_to_element` / the ROBULA+ XPath generator.
+    pub fn semantic_find(&self, query: &str, embedder: &dyn Embedder, top_k: usize) -> Vec<(usize, f32)> {
+        let query_vector = embedder.embed(query);
+
+        let mut scored: Vec<(usize, f32)> = self
+            .rows
+            .iter()
+            .map(|row| (row.node_index, cosine_similarity(&query_vector, &row.vector)))
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(top_k);
+        scored
+    }
+
+    /// The hash this index's cache rows are stored under.
+    pub fn tree_hash(&self) -> &str {
+        &self.tree_hash
+    }
+}
+
+/// Concatenate a node's name, control type, and key attributes into the short document
+/// string that gets embedded - close enough to the on-screen label that "the save
+/// button" and a node named "Speichern" (a `Button`) land close together in vector space
+/// once embedded.
+fn document_for(element: &SaveUIElement) -> String {
+    format!(
+        "{} {} {} {}",
+        element.get_name(),
+        element.get_localized_control_type(),
+        element.get_classname(),
+        element.get_automation_id(),
+    )
+}
+
+/// Hash every node's index and document string together so that any change to the tree
+/// (a renamed label, an added/removed element) invalidates the cache.
+fn hash_tree(tree: &UITree) -> String {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    tree.for_each(|node_index, element| {
+        node_index.hash(&mut hasher);
+        document_for(element).hash(&mut hasher);
+    });
+    format!("{:016x}", hasher.finish())
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+
+    dot / (norm_a * norm_b)
+}
+
+fn open_cache(cache_path: &Path) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(cache_path)?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS semantic_index (
+            tree_hash TEXT NOT NULL,
+            node_index INTEGER NOT NULL,
+            vector BLOB NOT NULL,
+            PRIMARY KEY (tree_hash, node_index)
+        )",
+        [],
+    )?;
+    Ok(conn)
+}
+
+fn load_cached_rows(conn: &Connection, tree_hash: &str) -> rusqlite::Result<Option<Vec<IndexedRow>>> {
+    let mut stmt = conn.prepare("SELECT node_index, vector FROM semantic_index WHERE tree_hash = ?1")?;
+    let rows: Vec<IndexedRow> = stmt
+        .query_map(params![tree_hash], |row| {
+            let node_index: i64 = row.get(0)?;
+            let blob: Vec<u8> = row.get(1)?;
+            Ok(IndexedRow {
+                node_index: node_index as usize,
+                vector: decode_vector(&blob),
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if rows.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(rows))
+    }
+}
+
+fn store_cached_rows(conn: &Connection, tree_hash: &str, rows: &[IndexedRow]) -> rusqlite::Result<()> {
+    for row in rows {
+        conn.execute(
+            "INSERT OR REPLACE INTO semantic_index (tree_hash, node_index, vector) VALUES (?1, ?2, ?3)",
+            params![tree_hash, row.node_index as i64, encode_vector(&row.vector)],
+        )?;
+    }
+    Ok(())
+}
+
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+fn decode_vector(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4).map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect()
+}
+
+/// A trivial `Embedder` that counts hashed words into buckets, with no model behind it - useful for
+/// wiring up `semantic_find` end to end before a real model/service is available.
+#[allow(dead_code)]
+struct HashBagEmbedder {
+    dimension: usize,
+}
+
+impl Embedder for HashBagEmbedder {
+    fn embed(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0_f32; self.dimension];
+        for word in text.split_whitespace() {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            word.to_lowercase().hash(&mut hasher);
+            let bucket = (hasher.finish() as usize) % self.dimension;
+            vector[bucket] += 1.0;
+        }
+        vector
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+}
@@ -0,0 +1,128 @@
+//! A generic tree structure with fast key-value lookup (not collision safe!)
+#![allow(dead_code)]
+use crate::UIHashMap;
+use serde::Serialize;
+
+// A generic node in a UITreeMap
+#[derive(Debug, Clone, Serialize)]
+pub struct UITreeNode<T> {
+    pub name: String,
+    pub key: String,
+    pub index: usize,
+    pub parent: usize,
+    pub children: Vec<usize>,
+    pub data: T,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UITreeMap<T> {
+    nodes: Vec<UITreeNode<T>>,
+    key_to_index: UIHashMap<String, usize>,
+}
+
+impl<T> UITreeMap<T> {
+    pub fn new(root_name: String, root_key: String, root_data: T) -> Self {
+        let root = UITreeNode {
+            name: root_name,
+            key: root_key.clone(),
+            index: 0,
+            parent: 0,
+            children: Vec::new(),
+            data: root_data,
+        };
+
+        let mut key_to_index = UIHashMap::default();
+        key_to_index.insert(root_key, 0);
+
+        Self {
+            nodes: vec![root],
+            key_to_index,
+        }
+    }
+
+    pub fn root(&self) -> usize {
+        0 // Root is always index 0
+    }
+
+    pub fn children(&self, index: usize) -> &[usize] {
+        &self.nodes[index].children
+    }
+
+    pub fn node(&self, index: usize) -> &UITreeNode<T> {
+        &self.nodes[index]
+    }
+
+    pub fn node_mut(&mut self, index: usize) -> &mut UITreeNode<T> {
+        &mut self.nodes[index]
+    }
+
+    pub fn add_child(&mut self, parent: usize, name: &str, key: &str, data: T) -> usize {
+        let index = self.nodes.len();
+        let node = UITreeNode {
+            name: name.to_string(),
+            key: key.to_string(),
+            index,
+            parent,
+            children: Vec::new(),
+            data,
+        };
+
+        self.key_to_index.insert(key.to_string(), index);
+        self.nodes[parent].children.push(index);
+        self.nodes.push(node);
+        index
+    }
+
+    /// Look up a node by the runtime id string it was inserted with.
+    pub fn get_element_by_runtime_id(&self, runtime_id: String) -> Option<&UITreeNode<T>> {
+        self.key_to_index.get(&runtime_id).map(|&index| &self.nodes[index])
+    }
+
+    pub fn get_path_to_element(&self, index: usize) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut current_index = index;
+        while current_index != 0 {
+            path.push(current_index);
+            current_index = self.nodes[current_index].parent;
+        }
+        path.reverse(); // Reverse to get the path from root to the node
+        path
+    }
+
+    /// Walks the tree and calls the callback on each node's data, immutably
+    pub fn for_each<F>(&self, mut callback: F)
+    where
+        F: FnMut(usize, &T),
+    {
+        for node in &self.nodes {
+            callback(node.index, &node.data);
+        }
+    }
+
+    /// Detach `index` and its whole subtree: unlinked from its parent's `children` and
+    /// removed from the key lookup, so neither a `children`/`node` walk nor
+    /// `get_element_by_runtime_id` can reach it any more. Returns every removed index
+    /// (the subtree root first, then its descendants).
+    ///
+    /// The node's slot in `nodes` is left in place rather than compacted - freeing it
+    /// would shift every index after it and invalidate anyone still holding one.
+    pub fn remove_subtree(&mut self, index: usize) -> Vec<usize> {
+        let mut removed = Vec::new();
+        let mut stack = vec![index];
+        while let Some(i) = stack.pop() {
+            removed.push(i);
+            stack.extend(self.nodes[i].children.iter().copied());
+        }
+
+        for &i in &removed {
+            self.key_to_index.remove(&self.nodes[i].key);
+        }
+
+        let parent = self.nodes[index].parent;
+        if parent != index {
+            self.nodes[parent].children.retain(|&c| c != index);
+        }
+
+        removed
+    }
+}
@@ -5,19 +5,17 @@ use crate::save_ui_element::SaveUIElement;
 // use crate::commons::FileWriter;
 use crate::{printfmt, UITreeMap};
 use xmlutil::xml::{XMLDomWriter, XMLDomNode};
-use xmlutil::xpath_gen::get_xpath_full_from_runtime_id; //get_xpath_from_runtime_id, 
+use xmlutil::xpath_gen::get_xpath_full_from_runtime_id; //get_xpath_from_runtime_id,
 use xmlutil::xpath_eval::eval_xpath;
-use xmlutil::XpathQueryResult;
-
 
+use serde::Serialize;
 
 use std::sync::mpsc::Sender;
 
 use uiautomation::core::UIAutomation;
 use uiautomation::{UIElement, UITreeWalker};
 
-
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct UIElementInTree {
     element_props: SaveUIElement,
     tree_index: usize,
@@ -32,12 +30,16 @@ impl UIElementInTree {
         &self.element_props
     }
 
+    pub fn get_element_props_mut(&mut self) -> &mut SaveUIElement {
+        &mut self.element_props
+    }
+
     pub fn get_tree_index(&self) -> usize {
         self.tree_index
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct UITree {
     tree: UITreeMap<SaveUIElement>,
     xml_dom_tree: String,
@@ -94,29 +96,79 @@ impl UITree {
 
     pub fn get_element_by_xpath(&self, xpath: &str) -> Option<&SaveUIElement> {
 
+        let elements = self.get_elements_by_xpath(xpath);
+
+        match elements.len() {
+            0 => None,
+            1 => elements.into_iter().next(),
+            count => {
+                printfmt!("Warning: XPath expression returned {} results, expected only 1 result. Returning None.", count);
+                None
+            }
+        }
+    }
+
+    /// Evaluate `xpath` and return every matching element, in document order.
+    ///
+    /// `get_element_by_xpath` bails out as soon as an expression matches more than one
+    /// node, which makes it unusable for anything that targets a set of elements (e.g.
+    /// "all buttons in this pane"). This resolves every match instead of just the first.
+    pub fn get_elements_by_xpath(&self, xpath: &str) -> Vec<&SaveUIElement> {
+
         // Patch the xpath with /@RtID if it is missing
         let xpath = if !xpath.ends_with("/@RtID") {xpath.to_string() + "/@RtID"} else {xpath.to_string()};
 
         let xpath_result = eval_xpath(xpath, self.get_xml_dom_tree().to_string());
-        
-        match xpath_result.get_result_count() {
-            0 => return None,
-            1 => {
-                let items = xpath_result.get_result_items();
-                let default_result = &XpathQueryResult::default();
-                let itm = items.get(0).unwrap_or(default_result);
-                let runtime_id = itm.get_item_value();
-                let ui_elem = self.get_tree().get_element_by_runtime_id(runtime_id).unwrap();
-                let ui_elem = ui_elem.data.get_element();
-                return Some(ui_elem);
-            },
-            _ => {
-                printfmt!("Warning: XPath expression returned {} results, expected only 1 result. Returning the first result.", xpath_result.get_result_count());
-                return None;
+
+        xpath_result.get_result_items().iter()
+            .filter_map(|itm| self.get_tree().get_element_by_runtime_id(itm.get_item_value()))
+            .map(|node| node.data.get_element())
+            .collect()
+    }
+
+    /// Return the indices of every node for which `predicate` returns `true`.
+    ///
+    /// This is the general-purpose building block behind `filter_by_query` for callers
+    /// that need to compose richer conditions (e.g. "enabled Buttons whose name
+    /// contains 'Save'") than a single substring match can express.
+    pub fn filter<F>(&self, mut predicate: F) -> Vec<usize>
+    where
+        F: FnMut(&SaveUIElement) -> bool,
+    {
+        let mut matches = Vec::new();
+        self.for_each(|index, element| {
+            if predicate(element) {
+                matches.push(index);
             }
-        }
+        });
+        matches
+    }
 
+    /// Case-insensitive substring search over name, localized control type, class name
+    /// and framework id - the same fields baked into each node's display label, exposed
+    /// here as structured accessors instead of forcing callers to re-parse that string.
+    pub fn filter_by_query(&self, query: &str) -> Vec<usize> {
+        let query = query.to_lowercase();
+        self.filter(|element| {
+            element.get_name().to_lowercase().contains(&query)
+                || element.get_localized_control_type().to_lowercase().contains(&query)
+                || element.get_classname().to_lowercase().contains(&query)
+                || element.get_framework_id().to_lowercase().contains(&query)
+        })
+    }
 
+    /// Regex search over name, control type, class name and automation id - the fields a
+    /// user is most likely to know part of when hunting for a specific element in a large
+    /// tree. Unlike `filter_by_query` this takes an already-compiled `Regex` so a caller
+    /// driving this from a text box (re-searching every keystroke) only pays the
+    /// compilation cost once per pattern change, not once per frame.
+    pub fn filter_by_regex(&self, re: &regex::Regex) -> Vec<usize> {
+        self.filter(|element| {
+            re.is_match(element.get_name())
+                || re.is_match(element.get_localized_control_type())
+                || re.is_match(element.get_classname())
+                || re.is_match(element.get_automation_id())
+        })
     }
 
 }
@@ -152,8 +204,10 @@ impl UITree {
 // }
 
 
-pub fn get_all_elements_xml(tx: Sender<UITree>, max_depth: Option<usize>, calling_window_caption: Option<String>) {   
-    
+/// Walk the desktop control view and build a `UITree`.
+#[tracing::instrument(skip(tx), fields(max_depth))]
+pub fn get_all_elements_xml(tx: Sender<UITree>, max_depth: Option<usize>, calling_window_caption: Option<String>) {
+
     let automation = UIAutomation::new().unwrap();
     // control view walker
     let walker = automation.get_control_view_walker().unwrap();
@@ -177,7 +231,7 @@ pub fn get_all_elements_xml(tx: Sender<UITree>, max_depth: Option<usize>, callin
     xml_root.set_attribute("RtID", runtime_id.as_str());
     xml_root.set_attribute("Name", root.get_name().unwrap_or("No name defined".to_string()).as_str());
 
-    if let Ok(_first_child) = walker.get_first_child(&root) {     
+    if let Ok(_first_child) = walker.get_first_child(&root) {
         // itarate over all child ui elements
         get_element(&mut tree, &mut ui_elements,  0, &walker, &root, xml_root, 0, 0, max_depth, calling_window_caption);
     }
@@ -185,6 +239,16 @@ pub fn get_all_elements_xml(tx: Sender<UITree>, max_depth: Option<usize>, callin
     // creating the XML DOM tree
     let xml_dom_tree = xml_writer.to_string().unwrap();
 
+    // Fill in each node's `xpath` now that the whole tree is built, then mirror it onto
+    // the flat `ui_elements` copies (`get_element` cloned `SaveUIElement` into both
+    // before the xpath existed, so they'd otherwise be left with `None`).
+    crate::xpath_gen::populate_xpaths(&mut tree);
+    for ui_element in &mut ui_elements {
+        if let Some(xpath) = tree.node(ui_element.get_tree_index()).data.get_xpath().cloned() {
+            ui_element.get_element_props_mut().set_xpath(xpath);
+        }
+    }
+
     // sorting the elements by z_order and then by ascending size of the bounding rectangle
     printfmt!("Sorting UI elements by size and z-order...");
     ui_elements.sort_by(|a, b| a.get_element_props().get_bounding_rect_size().cmp(&b.get_element_props().get_bounding_rect_size()));
@@ -211,7 +275,7 @@ fn get_element(mut tree: &mut UITreeMap<SaveUIElement>, mut ui_elements: &mut Ve
     if let Some(limit) = max_depth {
         if level > limit {
             return;
-        }    
+        }
     }
 
     if let Some(caption) = &calling_window_caption {
@@ -233,15 +297,14 @@ fn get_element(mut tree: &mut UITreeMap<SaveUIElement>, mut ui_elements: &mut Ve
     } else {
         ui_elem_props = SaveUIElement::new(element.clone(), level, z_order);
     }
-    
+
     let parent = tree.add_child(parent, item.as_str(), &runtime_id.as_str(), ui_elem_props.clone());
     let ui_elem_in_tree = UIElementInTree::new(ui_elem_props, parent);
     ui_elements.push(ui_elem_in_tree);
-        
+
     let curr_xml_dom_node = xml_dom_node.add_child(XMLDomNode::new(element.get_control_type().unwrap().as_str()));
     curr_xml_dom_node.set_attribute("RtID", runtime_id.as_str());
     curr_xml_dom_node.set_attribute("Name", element.get_name().unwrap_or("No name defined".to_string()).as_str());
-    
 
     // Walking the children of the current element
     if let Ok(child) = walker.get_first_child(&element) {
@@ -259,7 +322,7 @@ fn get_element(mut tree: &mut UITreeMap<SaveUIElement>, mut ui_elements: &mut Ve
             get_element(&mut tree, &mut ui_elements, parent, walker, &sibling, curr_xml_dom_node,  level + 1, z_order, max_depth, calling_window_caption.clone());
             next = sibling;
         }
-    }    
-    
+    }
+
 }
 
@@ -0,0 +1,103 @@
+//! Canonical xpath generation for a `SaveUIElement` ancestor chain.
+//!
+//! Mirrors the format `bromium`'s root-crate `xpath::get_path_to_element` parses back: a
+//! `ControlType` step per level plus `[@AutomationId="..."]` / `[@ClassName="..."]` /
+//! `[@Name="..."]` predicates with the same `\"` escaping, and a trailing `[n]`
+//! positional index whenever a sibling would otherwise render identically - so an xpath
+//! copied out of `uiexplore` resolves against the live UI tree with the same parser.
+
+use crate::save_ui_element::SaveUIElement;
+use crate::UITreeMap;
+
+fn escape_attribute_value(value: &str) -> String {
+    value.replace('"', "\\\"")
+}
+
+/// Render one element as the step `get_path_to_element` parses back: the control type,
+/// then - preferring `AutomationId` when it's set, falling back to `ClassName` and/or
+/// `Name` - the minimal predicate set that identifies it, then an optional trailing
+/// `[n]` positional index when those predicates don't already disambiguate it from a
+/// sibling.
+fn render_step(element: &SaveUIElement, sibling_index: Option<usize>) -> String {
+    let mut step = element.get_control_type().clone();
+
+    if !element.get_automation_id().is_empty() {
+        step.push_str(&format!("[@AutomationId=\\\"{}\\\"]", escape_attribute_value(element.get_automation_id())));
+    } else {
+        if !element.get_classname().is_empty() {
+            step.push_str(&format!("[@ClassName=\\\"{}\\\"]", escape_attribute_value(element.get_classname())));
+        }
+        if !element.get_name().is_empty() {
+            step.push_str(&format!("[@Name=\\\"{}\\\"]", escape_attribute_value(element.get_name())));
+        }
+    }
+
+    if let Some(index) = sibling_index {
+        step.push_str(&format!("[{}]", index));
+    }
+
+    step
+}
+
+/// Render the canonical xpath for `chain`, an ordered ancestor-to-target slice of
+/// `SaveUIElement` (the desktop root first, the target element last), with
+/// `sibling_indices[i]` giving the positional index to append to `chain[i]`'s step (or
+/// `None` when its predicates already disambiguate it from a sibling).
+///
+/// The leading segment rendered for `chain[0]` becomes the `/<root>/` that
+/// `get_path_to_element` always skips - it doesn't need to be unique, only present.
+pub fn generate_xpath(chain: &[&SaveUIElement], sibling_indices: &[Option<usize>]) -> String {
+    chain.iter().zip(sibling_indices.iter())
+        .fold(String::new(), |path, (element, index)| format!("{}/{}", path, render_step(element, *index)))
+}
+
+/// `index`'s 1-based position among the children of its parent that would render to the
+/// same step (ignoring any index), or `None` if its predicates are already unique among
+/// them. The desktop root itself (which has no parent) is never indexed.
+fn sibling_index_for(tree: &UITreeMap<SaveUIElement>, index: usize) -> Option<usize> {
+    if index == tree.root() {
+        return None;
+    }
+
+    let parent = tree.node(index).parent;
+    let step = render_step(&tree.node(index).data, None);
+    let matching: Vec<usize> = tree.children(parent).iter()
+        .cloned()
+        .filter(|&sibling| render_step(&tree.node(sibling).data, None) == step)
+        .collect();
+
+    if matching.len() <= 1 {
+        return None;
+    }
+    matching.iter().position(|&i| i == index).map(|position| position + 1)
+}
+
+fn collect_descendants(tree: &UITreeMap<SaveUIElement>, index: usize, out: &mut Vec<usize>) {
+    for &child in tree.children(index) {
+        out.push(child);
+        collect_descendants(tree, child, out);
+    }
+}
+
+/// Populate `xpath` on every node of `tree` by generating it from that node's own
+/// root-to-node ancestor chain - the generation-side counterpart of
+/// `bromium::xpath::get_path_to_element`, letting `uiexplore` show or copy an xpath for
+/// any element that resolves with the same parser.
+pub fn populate_xpaths(tree: &mut UITreeMap<SaveUIElement>) {
+    let root = tree.root();
+    let mut indices = vec![root];
+    collect_descendants(tree, root, &mut indices);
+
+    for index in indices {
+        let mut chain_indices = tree.get_path_to_element(index);
+        chain_indices.insert(0, root);
+
+        let sibling_indices: Vec<Option<usize>> = chain_indices.iter()
+            .map(|&i| sibling_index_for(tree, i))
+            .collect();
+        let chain: Vec<&SaveUIElement> = chain_indices.iter().map(|&i| &tree.node(i).data).collect();
+        let xpath = generate_xpath(&chain, &sibling_indices);
+
+        tree.node_mut(index).data.set_xpath(xpath);
+    }
+}
@@ -4,11 +4,17 @@ use crate::printfmt;
 
 
 use std::thread;
-use std::sync::mpsc::{channel, Receiver, Sender};
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
 
+use regex::Regex;
+use uiautomation::controls::ControlType;
 use uiautomation::types::Handle;
 use uiautomation::{UIAutomation, UIElement};
 
+use uitree::{UITreeMap, SaveUIElementXML as SaveUIElement};
+
 pub use win_event_hook::events::{Event, NamedEvent};
 use win_event_hook::WinEventHook;
 use win_event_hook::handles::OpaqueHandle;
@@ -16,88 +22,298 @@ use win_event_hook::handles::builtins::WindowHandle;
 
 use windows::Win32::Foundation::HWND;
 
+/// How long an HWND must go quiet before its latest pending event is flushed - long
+/// enough to coalesce the dense `ObjectLocationChange`/create-destroy bursts a single
+/// window animation fires, short enough that callers still see events promptly.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// The most recent not-yet-flushed event for one HWND, and when it was last replaced -
+/// `check_for_events` resets `last_seen` every time a new event supersedes this one, so
+/// only an HWND that's gone quiet for [`WinEventMonitor::debounce`] gets flushed.
+struct PendingEvent {
+    event: Event,
+    hwnd: HWND,
+    last_seen: Instant,
+}
+
+fn is_object_create(event: &Event) -> bool {
+    matches!(event, Event::Named(NamedEvent::ObjectCreate))
+}
+
+fn is_object_destroy(event: &Event) -> bool {
+    matches!(event, Event::Named(NamedEvent::ObjectDestroy))
+}
+
+/// A single match rule a [`WinEventMonitorConfig`] registers, evaluated against an
+/// event's resolved `UIElement` - an event only reaches the output once every
+/// registered predicate passes, the same AND-of-rules a client would register against
+/// a window-server style event matcher rather than filtering the raw event stream
+/// itself.
+pub enum EventPredicate {
+    ClassName(String),
+    ProcessId(u32),
+    ControlType(ControlType),
+    /// Case-insensitive substring match against the element's name.
+    NameContains(String),
+    NameRegex(Regex),
+}
+
+impl EventPredicate {
+    fn matches(&self, element: &UIElement) -> bool {
+        match self {
+            EventPredicate::ClassName(expected) => element.get_classname().map(|c| &c == expected).unwrap_or(false),
+            EventPredicate::ProcessId(expected) => element.get_process_id().map(|pid| pid as u32 == *expected).unwrap_or(false),
+            EventPredicate::ControlType(expected) => element.get_control_type().map(|c| c == *expected).unwrap_or(false),
+            EventPredicate::NameContains(needle) => element.get_name()
+                .map(|name| name.to_lowercase().contains(&needle.to_lowercase()))
+                .unwrap_or(false),
+            EventPredicate::NameRegex(pattern) => element.get_name().map(|name| pattern.is_match(&name)).unwrap_or(false),
+        }
+    }
+}
+
+/// Declarative configuration for a [`WinEventMonitor`]: which `NamedEvent`s to
+/// subscribe to, how long to debounce bursts, and which [`EventPredicate`]s a resolved
+/// event must satisfy to reach `check_for_events`'s output - lets a caller register
+/// match rules instead of receiving every raw event and filtering it themselves.
+pub struct WinEventMonitorConfig {
+    events: Vec<NamedEvent>,
+    debounce: Duration,
+    predicates: Vec<EventPredicate>,
+}
+
+impl WinEventMonitorConfig {
+    pub fn new() -> Self {
+        WinEventMonitorConfig { events: default_named_events(), debounce: DEFAULT_DEBOUNCE, predicates: Vec::new() }
+    }
+
+    /// Subscribe to exactly `events` instead of [`default_named_events`].
+    pub fn with_events(mut self, events: Vec<NamedEvent>) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    pub fn matching_class_name(mut self, class_name: impl Into<String>) -> Self {
+        self.predicates.push(EventPredicate::ClassName(class_name.into()));
+        self
+    }
+
+    pub fn matching_process_id(mut self, process_id: u32) -> Self {
+        self.predicates.push(EventPredicate::ProcessId(process_id));
+        self
+    }
+
+    pub fn matching_control_type(mut self, control_type: ControlType) -> Self {
+        self.predicates.push(EventPredicate::ControlType(control_type));
+        self
+    }
+
+    pub fn matching_name_contains(mut self, substring: impl Into<String>) -> Self {
+        self.predicates.push(EventPredicate::NameContains(substring.into()));
+        self
+    }
+
+    pub fn matching_name_regex(mut self, pattern: Regex) -> Self {
+        self.predicates.push(EventPredicate::NameRegex(pattern));
+        self
+    }
+}
+
+impl Default for WinEventMonitorConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A push-mode event handler for [`WinEventMonitor::watch`], modeled on the `notify`
+/// crate's `EventFn` - invoked once per flushed [`WinEvtMonitorEvent`] instead of
+/// requiring the caller to drain `check_for_events` in a poll loop.
+pub trait EventFn: FnMut(WinEvtMonitorEvent) + Send {}
+impl<F: FnMut(WinEvtMonitorEvent) + Send> EventFn for F {}
+
 pub struct WinEventMonitor {
     hook: WinEventHook,
     rx_channel: Receiver<WinEventInfo>,
-    last_hwnd: HWND,
     mouse_hwnd: HWND,
     uia: UIAutomation,
-
+    /// How long an HWND must be quiescent before its pending event is flushed. See
+    /// [`DEFAULT_DEBOUNCE`].
+    debounce: Duration,
+    /// One pending event per HWND awaiting its debounce window - see [`PendingEvent`].
+    pending: HashMap<isize, PendingEvent>,
+    /// Match rules a resolved event must satisfy to reach `check_for_events`'s output.
+    predicates: Vec<EventPredicate>,
 }
 
 impl WinEventMonitor {
 
     pub fn new() -> Self {
-    
+        Self::with_config(WinEventMonitorConfig::new())
+    }
+
+    /// As [`WinEventMonitor::new`], but lets the caller override the debounce window
+    /// instead of always using [`DEFAULT_DEBOUNCE`].
+    pub fn new_with_debounce(debounce: Duration) -> Self {
+        Self::with_config(WinEventMonitorConfig::new().with_debounce(debounce))
+    }
+
+    /// Build a monitor from a declarative [`WinEventMonitorConfig`] - which events to
+    /// subscribe to, the debounce window, and which [`EventPredicate`]s a resolved
+    /// event must satisfy to reach the output.
+    pub fn with_config(config: WinEventMonitorConfig) -> Self {
+
         // The mouse cursor constant (0x0) to filter mouse events later on
         let mouse_hwnd: HWND = HWND::default();
-            
+
         // create the hook
-        let (mut hook, rx) =  create_hook();
-        
-        let mut last_hwnd: HWND = HWND::default();
+        let (hook, rx) =  create_hook(&config.events);
 
         // Initialize UIAutomation
         let uia = get_ui_automation_instance().unwrap();
-        
 
-        WinEventMonitor { hook, rx_channel: rx, last_hwnd, mouse_hwnd, uia}
+        WinEventMonitor {
+            hook,
+            rx_channel: rx,
+            mouse_hwnd,
+            uia,
+            debounce: config.debounce,
+            pending: HashMap::new(),
+            predicates: config.predicates,
+        }
+
 
-    
     }
-        
+
     pub fn check_for_events(&mut self) -> Vec<WinEvtMonitorEvent> {
-        let mut output: Vec<WinEvtMonitorEvent> = Vec::new();
+        // Drain every raw event into `pending`, coalescing bursts for the same HWND
+        // instead of resolving and emitting each one individually.
+        while let Ok(event_info) = self.rx_channel.try_recv() {
+            self.record_event(event_info);
+        }
 
+        self.flush_ready()
+    }
 
-        // Main event processing 
-        let mut i = 0;
-        let mut name = "".to_string();
-        let mut rt_id: Vec<i32> = vec![0, 0, 0, 0];
-        // Check for new events
-        // match self.rx_channel.try_recv() {
-        while let Ok(event_info) = self.rx_channel.try_recv() {
-            // Ok(event_info) => {
-                let hwnd = *event_info.hwnd;
-                if hwnd.0 != self.mouse_hwnd.0 {
-                    
-                    if self.last_hwnd.0 != hwnd.0 {
-                        self.last_hwnd = hwnd;
-                        let handle: Handle = Handle::from(hwnd.0 as isize);
-                        let element: Result<UIElement, uiautomation::Error> = self.uia.element_from_handle(handle);
-                        match element {
-                            Ok(e) => {
-                                name = e.get_name().unwrap_or("".to_string());
-                                rt_id = e.get_runtime_id().unwrap_or(vec![0, 0, 0, 0]);
-                            }
-                            Err(_e) => {
-                                // name = format!("Failed to get element from handle: {:?}", e);
-                                name = "invalid hwnd".to_string();
-                            }
-                        }
-                        // name = element.get_name().unwrap_or("".to_string());
-                    }
-                    println!("Received event: {:?} on hwnd: {:?} ({})", event_info.event, hwnd.0, name.clone());
-                    let evt_monitor_event = WinEvtMonitorEvent {
-                        event: event_info.event,
-                        hwnd: *event_info.hwnd,
-                        ui_element_name: name.clone(),
-                        ui_element_runtime_id: rt_id.clone(),
-                    };
-                    output.push(evt_monitor_event);
+    /// Fold one raw event into `pending`, coalescing bursts for the same HWND instead of
+    /// resolving and emitting each one individually. Shared by `check_for_events`'s
+    /// drain loop and `watch`'s push-driven one.
+    fn record_event(&mut self, event_info: WinEventInfo) {
+        let hwnd = *event_info.hwnd;
+        if hwnd.0 == self.mouse_hwnd.0 {
+            return;
+        }
+
+        let key = hwnd.0 as isize;
+        if let Some(pending) = self.pending.get(&key) {
+            if is_object_create(&pending.event) && is_object_destroy(&event_info.event) {
+                // A create immediately undone by a destroy within the debounce window
+                // is a net no-op - drop both instead of emitting either.
+                self.pending.remove(&key);
+                return;
+            }
+        }
+
+        self.pending.insert(key, PendingEvent {
+            event: event_info.event,
+            hwnd,
+            last_seen: Instant::now(),
+        });
+    }
+
+    /// Flush whatever in `pending` has gone quiet for at least `debounce`, resolving its
+    /// UIA element once now rather than once per raw event that arrived for it.
+    fn flush_ready(&mut self) -> Vec<WinEvtMonitorEvent> {
+        let now = Instant::now();
+        let ready: Vec<isize> = self.pending.iter()
+            .filter(|(_, pending)| now.duration_since(pending.last_seen) >= self.debounce)
+            .map(|(&key, _)| key)
+            .collect();
+
+        let mut output: Vec<WinEvtMonitorEvent> = Vec::with_capacity(ready.len());
+        for key in ready {
+            let Some(pending) = self.pending.remove(&key) else { continue };
+            let element = self.resolve_element(pending.hwnd);
+
+            // A registered predicate only makes sense against a resolved element - an
+            // HWND we couldn't resolve can't satisfy any of them.
+            if !self.predicates.is_empty() {
+                let passes = element.as_ref().is_some_and(|e| self.predicates.iter().all(|p| p.matches(e)));
+                if !passes {
+                    continue;
                 }
             }
-            // Err(std::sync::mpsc::TryRecvError::Empty) => {
-            //     // No events available, sleep for a bit
-            //     // thread::sleep(std::time::Duration::from_secs(1));
-            // }
-            // Err(e) => {
-            //     eprintln!("Channel error: {}", e);
-            // }
-        // }
+
+            let (name, runtime_id) = match &element {
+                Some(e) => (e.get_name().unwrap_or("".to_string()), e.get_runtime_id().unwrap_or(vec![0, 0, 0, 0])),
+                None => ("invalid hwnd".to_string(), vec![0, 0, 0, 0]),
+            };
+            println!("Flushing debounced event: {:?} on hwnd: {:?} ({})", pending.event, pending.hwnd.0, name);
+            output.push(WinEvtMonitorEvent {
+                event: pending.event,
+                hwnd: pending.hwnd,
+                ui_element_name: name,
+                ui_element_runtime_id: runtime_id,
+            });
+        }
+
         output
     }
 
+    /// Resolve `hwnd` to its live `UIElement` - called once per HWND per debounce
+    /// flush instead of once per raw event, which is where the bulk of the redundant
+    /// UIA lookups this debouncing layer avoids used to come from.
+    fn resolve_element(&self, hwnd: HWND) -> Option<UIElement> {
+        let handle: Handle = Handle::from(hwnd.0 as isize);
+        self.uia.element_from_handle(handle).ok()
+    }
+
+    /// Hand this monitor off to a dedicated thread that blocks on the hook's event
+    /// channel and invokes `handler` for each flushed event, signaling `waker` (if
+    /// given) once per non-empty batch - lets a caller integrate this monitor into an
+    /// external event loop (e.g. a winit-style `EventLoopProxy::send_event`). Unlike
+    /// `check_for_events`, which a caller has to poll themselves, this thread wakes as
+    /// soon as the WinEvent hook's own dedicated thread pushes a raw event onto the
+    /// channel rather than sitting on a fixed `debounce`-paced sleep regardless of
+    /// whether anything happened; `debounce` is only used as the `recv_timeout` bound so
+    /// a pending event with no further activity still gets flushed once it's gone quiet.
+    /// Consumes `self`: the existing polling API is still there for a `WinEventMonitor`
+    /// that isn't handed off this way.
+    pub fn watch<F>(mut self, mut handler: F, waker: Option<Sender<()>>) -> thread::JoinHandle<()>
+    where
+        F: EventFn + 'static,
+    {
+        thread::spawn(move || loop {
+            match self.rx_channel.recv_timeout(self.debounce) {
+                Ok(event_info) => self.record_event(event_info),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            // Fold in whatever else has already piled up since we were last woken,
+            // instead of handling one event per wakeup.
+            while let Ok(event_info) = self.rx_channel.try_recv() {
+                self.record_event(event_info);
+            }
+
+            let events = self.flush_ready();
+            if events.is_empty() {
+                continue;
+            }
+
+            for event in events {
+                handler(event);
+            }
+
+            if let Some(waker) = &waker {
+                let _ = waker.send(());
+            }
+        })
+    }
+
 }
 
 impl Drop for WinEventMonitor {
@@ -117,6 +333,26 @@ pub struct WinEvtMonitorEvent {
     ui_element_runtime_id: Vec<i32>,
 }
 
+impl serde::Serialize for WinEvtMonitorEvent {
+    /// Written by hand rather than derived: `Event` is a `win_event_hook` type and
+    /// `HWND` a raw `windows` handle, neither of which implement `Serialize` - here
+    /// they're flattened to their `Debug` string and raw pointer value respectively so
+    /// a stream of these can be written out as JSONL for replay/diffing.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("WinEvtMonitorEvent", 4)?;
+        state.serialize_field("event", &format!("{:?}", self.event))?;
+        state.serialize_field("hwnd", &(self.hwnd.0 as isize))?;
+        state.serialize_field("ui_element_name", &self.ui_element_name)?;
+        state.serialize_field("ui_element_runtime_id", &self.ui_element_runtime_id)?;
+        state.end()
+    }
+}
+
 impl WinEvtMonitorEvent {
     
     pub fn get_event(&self) -> Event {
@@ -136,6 +372,222 @@ impl WinEvtMonitorEvent {
     }
 }
 
+/// Join a `get_runtime_id()`-style id into the dash-separated string `UITreeMap` keys
+/// its nodes with elsewhere in the codebase (e.g. `get_xpath_full_from_runtime_id`).
+fn runtime_id_key(runtime_id: &[i32]) -> String {
+    runtime_id.iter().map(|x| x.to_string()).collect::<Vec<String>>().join("-")
+}
+
+/// One incremental change folded into a [`LiveUITree`] by a single [`LiveUITree::poll`]
+/// call: node indices added, removed, or refreshed in place - the same add/remove/update
+/// delta model an accessibility tree adapter pushes to its consumers, instead of a full
+/// rescan a caller would otherwise have to diff against the previous tree themselves.
+#[derive(Debug, Default)]
+pub struct UITreeUpdate {
+    pub added: Vec<usize>,
+    pub removed: Vec<usize>,
+    pub changed: Vec<usize>,
+}
+
+/// A `UITreeMap<SaveUIElement>` snapshot kept in sync with the live desktop by applying
+/// [`WinEventMonitor`] events in place instead of rebuilding the whole tree on every
+/// change: `ObjectCreate` resolves the new element and splices it under its parent
+/// HWND, `ObjectDestroy` prunes the subtree, and `ObjectLocationChange` refreshes just
+/// the geometry fields of the node that moved. Nodes are keyed by
+/// `ui_element_runtime_id` the same way the rest of the tree tooling keys them.
+pub struct LiveUITree {
+    tree: UITreeMap<SaveUIElement>,
+    monitor: WinEventMonitor,
+    uia: UIAutomation,
+    /// Maps a window's native handle to the tree index it was last inserted/updated at,
+    /// so an `ObjectCreate`/`ObjectDestroy`/`ObjectLocationChange` (which only carry an
+    /// HWND) doesn't need to search the whole tree to find the node it applies to.
+    hwnd_index: HashMap<isize, usize>,
+}
+
+impl LiveUITree {
+    /// Wrap an initial `tree` snapshot with `monitor`, indexing every node already in
+    /// `tree` by its native window handle. Returns `None` if a `UIAutomation` instance
+    /// for resolving future events can't be created.
+    pub fn new(tree: UITreeMap<SaveUIElement>, monitor: WinEventMonitor) -> Option<Self> {
+        let uia = get_ui_automation_instance()?;
+
+        let mut hwnd_index = HashMap::new();
+        tree.for_each(|index, element| {
+            hwnd_index.insert(element.get_handle(), index);
+        });
+
+        Some(LiveUITree { tree, monitor, uia, hwnd_index })
+    }
+
+    pub fn tree(&self) -> &UITreeMap<SaveUIElement> {
+        &self.tree
+    }
+
+    /// Poll the underlying [`WinEventMonitor`] and fold whatever debounced events it
+    /// returns into the cached tree, returning what changed instead of forcing callers
+    /// to rebuild the tree and diff it against the previous one themselves.
+    pub fn poll(&mut self) -> UITreeUpdate {
+        let mut update = UITreeUpdate::default();
+
+        for event in self.monitor.check_for_events() {
+            match event.get_event() {
+                Event::Named(NamedEvent::ObjectDestroy) => self.apply_destroy(event.get_hwnd(), &mut update),
+                Event::Named(NamedEvent::ObjectCreate) => self.apply_create(event.get_hwnd(), &mut update),
+                Event::Named(NamedEvent::ObjectLocationChange) => self.apply_location_change(event.get_hwnd(), &mut update),
+                _ => {}
+            }
+        }
+
+        update
+    }
+
+    fn apply_destroy(&mut self, hwnd: HWND, update: &mut UITreeUpdate) {
+        let Some(&index) = self.hwnd_index.get(&(hwnd.0 as isize)) else { return };
+
+        for removed in self.tree.remove_subtree(index) {
+            let removed_handle = self.tree.node(removed).data.get_handle();
+            self.hwnd_index.remove(&removed_handle);
+            update.removed.push(removed);
+        }
+    }
+
+    fn apply_create(&mut self, hwnd: HWND, update: &mut UITreeUpdate) {
+        let handle = hwnd.0 as isize;
+        if self.hwnd_index.contains_key(&handle) {
+            // Already tracked (e.g. a duplicate create for a window we haven't seen a
+            // matching destroy for yet) - nothing new to splice in.
+            return;
+        }
+
+        let Ok(element) = self.uia.element_from_handle(Handle::from(handle)) else { return };
+
+        let parent_index = self.uia.get_control_view_walker().ok()
+            .and_then(|walker| walker.get_parent(&element).ok())
+            .map(|parent| parent.get_native_window_handle().unwrap_or(Handle::from(0isize)).into())
+            .and_then(|parent_handle: isize| self.hwnd_index.get(&parent_handle).copied())
+            .unwrap_or_else(|| self.tree.root());
+
+        let level = self.tree.node(parent_index).data.get_level() + 1;
+        let z_order = self.tree.children(parent_index).len();
+        let props = SaveUIElement::new(element, level, z_order);
+        let key = runtime_id_key(props.get_runtime_id());
+        let name = props.get_name().clone();
+        let node_handle = props.get_handle();
+
+        let index = self.tree.add_child(parent_index, &name, &key, props);
+        self.hwnd_index.insert(node_handle, index);
+        update.added.push(index);
+    }
+
+    fn apply_location_change(&mut self, hwnd: HWND, update: &mut UITreeUpdate) {
+        let Some(&index) = self.hwnd_index.get(&(hwnd.0 as isize)) else { return };
+        let Ok(element) = self.uia.element_from_handle(Handle::from(hwnd.0 as isize)) else { return };
+        let Ok(rect) = element.get_bounding_rectangle() else { return };
+
+        self.tree.node_mut(index).data.set_bounding_rectangle(rect);
+        update.changed.push(index);
+    }
+}
+
+fn is_foreground_or_focus(event: &Event) -> bool {
+    matches!(event, Event::Named(NamedEvent::SystemForeground) | Event::Named(NamedEvent::ObjectFocus))
+}
+
+/// A resolved focused element: the HWND it lives in plus its UIA name and runtime id -
+/// the same identifying fields [`WinEvtMonitorEvent`] surfaces for a raw event.
+#[derive(Debug, Clone)]
+pub struct FocusedElement {
+    hwnd: HWND,
+    name: String,
+    runtime_id: Vec<i32>,
+}
+
+impl FocusedElement {
+    pub fn get_hwnd(&self) -> HWND {
+        self.hwnd
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn get_runtime_id(&self) -> Vec<i32> {
+        self.runtime_id.clone()
+    }
+}
+
+/// A net focus transition detected by [`FocusController::check_for_events`]: the
+/// previously-focused element (`None` before the first transition) and the one that
+/// now holds focus.
+#[derive(Debug)]
+pub struct FocusChanged {
+    pub previous: Option<FocusedElement>,
+    pub current: FocusedElement,
+}
+
+/// Tracks the single element that currently has desktop-wide focus, independent of the
+/// `ObjectShow`/`ObjectHide` churn a transient tooltip or popup fires as it steals and
+/// returns focus - borrowed from the focus-controller concept in window-server designs,
+/// where one component is the source of truth for "what has focus right now" instead of
+/// every interested party reasoning about raw focus/show/hide events itself.
+pub struct FocusController {
+    monitor: WinEventMonitor,
+    uia: UIAutomation,
+    current: Option<FocusedElement>,
+}
+
+impl FocusController {
+    /// Build a controller watching only `SystemForeground`/`ObjectFocus`. Returns
+    /// `None` if a `UIAutomation` instance for resolving the true focused element
+    /// can't be created.
+    pub fn new() -> Option<Self> {
+        let monitor = WinEventMonitor::with_config(
+            WinEventMonitorConfig::new().with_events(vec![NamedEvent::SystemForeground, NamedEvent::ObjectFocus]),
+        );
+        let uia = get_ui_automation_instance()?;
+        Some(FocusController { monitor, uia, current: None })
+    }
+
+    /// The element that currently has focus, if a transition has been observed yet.
+    pub fn get_focused_element(&self) -> Option<&FocusedElement> {
+        self.current.as_ref()
+    }
+
+    /// Poll for `SystemForeground`/`ObjectFocus` events and re-resolve the desktop's
+    /// actual focused element for each one rather than trusting the triggering HWND -
+    /// re-querying is what collapses a tooltip/popup's steal-then-return-focus churn
+    /// into a single net change instead of two.
+    pub fn check_for_events(&mut self) -> Vec<FocusChanged> {
+        let mut changes = Vec::new();
+
+        for event in self.monitor.check_for_events() {
+            if !is_foreground_or_focus(&event.get_event()) {
+                continue;
+            }
+
+            let Ok(focused) = self.uia.get_focused_element() else { continue };
+            let runtime_id = focused.get_runtime_id().unwrap_or(vec![0, 0, 0, 0]);
+
+            if self.current.as_ref().is_some_and(|current| current.runtime_id == runtime_id) {
+                continue;
+            }
+
+            let handle: isize = focused.get_native_window_handle().unwrap_or(Handle::from(0 as isize)).into();
+            let candidate = FocusedElement {
+                hwnd: HWND(handle as *mut _),
+                name: focused.get_name().unwrap_or_default(),
+                runtime_id,
+            };
+
+            let previous = self.current.replace(candidate.clone());
+            changes.push(FocusChanged { previous, current: candidate });
+        }
+
+        changes
+    }
+}
+
 
 
 #[derive(Debug)]
@@ -153,7 +605,43 @@ fn create_event_handler(tx: Sender<WinEventInfo>) -> impl Fn(Event, OpaqueHandle
     }
 }
 
-fn create_hook() -> (WinEventHook, Receiver<WinEventInfo>) {
+/// The `NamedEvent`s a [`WinEventMonitorConfig`] subscribes to unless overridden with
+/// [`WinEventMonitorConfig::with_events`].
+fn default_named_events() -> Vec<NamedEvent> {
+    vec![
+        // A hidden object is shown. The system sends this event for the following user interface elements: caret, cursor, and window object. Server applications send this event for their accessible objects.
+        // Clients assume that when this event is sent by a parent object, all child objects are already displayed. Therefore, server applications do not send this event for the child objects.
+        // Hidden objects include the STATE_SYSTEM_INVISIBLE flag; shown objects do not include this flag. The EVENT_OBJECT_SHOW event also indicates that the STATE_SYSTEM_INVISIBLE flag is cleared. Therefore, servers do not send the EVENT_STATE_CHANGE event in this case.
+        NamedEvent::ObjectShow,
+        // An object is hidden. The system sends this event for the following user interface elements: caret and cursor. Server applications send this event for their accessible objects.
+        // When this event is generated for a parent object, all child objects are already hidden. Server applications do not send this event for the child objects.
+        // Hidden objects include the STATE_SYSTEM_INVISIBLE flag; shown objects do not include this flag. The EVENT_OBJECT_HIDE event also indicates that the STATE_SYSTEM_INVISIBLE flag is set. Therefore, servers do not send the EVENT_STATE_CHANGE event in this case.
+        NamedEvent::ObjectHide,
+        // An object has been created. The system sends this event for the following user interface elements: caret, header control, list-view control, tab control, toolbar control, tree view control, and window object. Server applications send this event for their accessible objects.
+        // Before sending the event for the parent object, servers must send it for all of an object's child objects. Servers must ensure that all child objects are fully created and ready to accept IAccessible calls from clients before the parent object sends this event.
+        // Because a parent object is created after its child objects, clients must make sure that an object's parent has been created before calling IAccessible::get_accParent, particularly if in-context hook functions are used.
+        NamedEvent::ObjectCreate,
+        // An object has been destroyed. The system sends this event for the following user interface elements: caret, header control, list-view control, tab control, toolbar control, tree view control, and window object. Server applications send this event for their accessible objects.
+        // Clients assume that all of an object's children are destroyed when the parent object sends this event.
+        // After receiving this event, clients do not call an object's IAccessible properties or methods. However, the interface pointer must remain valid as long as there is a reference count on it (due to COM rules), but the UI element may no longer be present. Further calls on the interface pointer may return failure errors; to prevent this, servers create proxy objects and monitor their life spans.
+        NamedEvent::ObjectDestroy,
+        // An object has changed location, shape, or size. The system sends this event for the following user interface elements: caret and window objects. Server applications send this event for their accessible objects.
+        // This event is generated in response to a change in the top-level object within the object hierarchy; it is not generated for any children that the object might have. For example, if the user resizes a window, the system sends this notification for the window, but not for the menu bar, title bar, scroll bar, or other objects that have also changed.
+        // The system does not send this event for every non-floating child window when the parent moves. However, if an application explicitly resizes child windows as a result of resizing the parent window, the system sends multiple events for the resized children.
+        // If an object's State property is set to STATE_SYSTEM_FLOATING, the server sends EVENT_OBJECT_LOCATIONCHANGE whenever the object changes location. If an object does not have this state, servers only trigger this event when the object moves in relation to its parent. For this event notification, the idChild parameter of the WinEventProc callback function identifies the child object that has changed.
+        NamedEvent::ObjectLocationChange,
+        // A window object is about to be restored. This event is sent by the system, never by servers.
+        NamedEvent::SystemMinimizeEnd,
+        // The movement or resizing of a window has finished. This event is sent by the system, never by servers.
+        NamedEvent::SystemMoveSizeEnd,
+        // The foreground window has changed. The system sends this event even if the foreground window has changed to another window in the same thread. Server applications never send this event.
+        NamedEvent::SystemForeground,
+        // The object has received the keyboard focus. The system sends this event for the following user interface elements: menu bar, pop-up menu, list-view control, and tree view control. Server applications send this event for their accessible objects.
+        NamedEvent::ObjectFocus,
+    ]
+}
+
+fn create_hook(events: &[NamedEvent]) -> (WinEventHook, Receiver<WinEventInfo>) {
     // Create channel for communication
     let (tx, rx): (Sender<WinEventInfo>, Receiver<WinEventInfo>) = channel();
 
@@ -161,33 +649,7 @@ fn create_hook() -> (WinEventHook, Receiver<WinEventInfo>) {
     let config = win_event_hook::Config::builder()
         .skip_own_process()
         .with_dedicated_thread()
-        .with_events(vec![
-            // A hidden object is shown. The system sends this event for the following user interface elements: caret, cursor, and window object. Server applications send this event for their accessible objects.
-            // Clients assume that when this event is sent by a parent object, all child objects are already displayed. Therefore, server applications do not send this event for the child objects.
-            // Hidden objects include the STATE_SYSTEM_INVISIBLE flag; shown objects do not include this flag. The EVENT_OBJECT_SHOW event also indicates that the STATE_SYSTEM_INVISIBLE flag is cleared. Therefore, servers do not send the EVENT_STATE_CHANGE event in this case.
-            Event::Named(NamedEvent::ObjectShow),
-            // An object is hidden. The system sends this event for the following user interface elements: caret and cursor. Server applications send this event for their accessible objects.
-            // When this event is generated for a parent object, all child objects are already hidden. Server applications do not send this event for the child objects.
-            // Hidden objects include the STATE_SYSTEM_INVISIBLE flag; shown objects do not include this flag. The EVENT_OBJECT_HIDE event also indicates that the STATE_SYSTEM_INVISIBLE flag is set. Therefore, servers do not send the EVENT_STATE_CHANGE event in this case.            
-            Event::Named(NamedEvent::ObjectHide),
-            // An object has been created. The system sends this event for the following user interface elements: caret, header control, list-view control, tab control, toolbar control, tree view control, and window object. Server applications send this event for their accessible objects.
-            // Before sending the event for the parent object, servers must send it for all of an object's child objects. Servers must ensure that all child objects are fully created and ready to accept IAccessible calls from clients before the parent object sends this event.
-            // Because a parent object is created after its child objects, clients must make sure that an object's parent has been created before calling IAccessible::get_accParent, particularly if in-context hook functions are used.
-            Event::Named(NamedEvent::ObjectCreate),
-            // An object has been destroyed. The system sends this event for the following user interface elements: caret, header control, list-view control, tab control, toolbar control, tree view control, and window object. Server applications send this event for their accessible objects.
-            // Clients assume that all of an object's children are destroyed when the parent object sends this event.
-            // After receiving this event, clients do not call an object's IAccessible properties or methods. However, the interface pointer must remain valid as long as there is a reference count on it (due to COM rules), but the UI element may no longer be present. Further calls on the interface pointer may return failure errors; to prevent this, servers create proxy objects and monitor their life spans.            
-            Event::Named(NamedEvent::ObjectDestroy),
-            // An object has changed location, shape, or size. The system sends this event for the following user interface elements: caret and window objects. Server applications send this event for their accessible objects.
-            // This event is generated in response to a change in the top-level object within the object hierarchy; it is not generated for any children that the object might have. For example, if the user resizes a window, the system sends this notification for the window, but not for the menu bar, title bar, scroll bar, or other objects that have also changed.
-            // The system does not send this event for every non-floating child window when the parent moves. However, if an application explicitly resizes child windows as a result of resizing the parent window, the system sends multiple events for the resized children.
-            // If an object's State property is set to STATE_SYSTEM_FLOATING, the server sends EVENT_OBJECT_LOCATIONCHANGE whenever the object changes location. If an object does not have this state, servers only trigger this event when the object moves in relation to its parent. For this event notification, the idChild parameter of the WinEventProc callback function identifies the child object that has changed.
-            Event::Named(NamedEvent::ObjectLocationChange),
-            // A window object is about to be restored. This event is sent by the system, never by servers.
-            Event::Named(NamedEvent::SystemMinimizeEnd),
-            // The movement or resizing of a window has finished. This event is sent by the system, never by servers.
-            Event::Named(NamedEvent::SystemMoveSizeEnd),
-        ])
+        .with_events(events.iter().cloned().map(Event::Named).collect())
         .finish();
 
     // Create handler and install hook
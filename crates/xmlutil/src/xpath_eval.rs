@@ -1,7 +1,4 @@
-use xee_xpath::error::Error;
-use xee_xpath::context::StaticContextBuilder;
-use xee_xpath::Itemable;
-use xee_xpath::Query;
+use roxmltree::{Document, Node};
 
 pub struct XpathResult {
     result_count: usize,
@@ -12,127 +9,207 @@ impl XpathResult {
     fn new(result_count: usize, result: Vec<String>) -> Self {
         XpathResult { result_count, result }
     }
-    
+
     pub fn get_result_count(&self) -> usize {
         self.result_count
     }
-    
+
     pub fn get_result_items(&self) -> Vec<String> {
         self.result.clone()
     }
 }
 
+/// One `[...]` predicate on a location step: a positional test (`[2]`, `[last()]`) or an
+/// attribute-equality test (`[@type="myHeading"]`).
+#[derive(Debug, Clone)]
+enum Predicate {
+    Position(usize),
+    Last,
+    AttrEquals(String, String),
+}
 
-
-pub fn eval_xpath(expr: String, srcxml: String) -> XpathResult {
-    
-    let input_xml = srcxml.as_str();
-
-    let mut documents = xee_xpath::Documents::new();
-    let doc = documents.add_string_without_uri(&input_xml).unwrap();
-
-    let static_context_builder = make_static_context_builder(
-        None,
-        &[],
-    ).unwrap();
-
-    let queries = xee_xpath::Queries::new(static_context_builder);
-    let res = execute_query(expr.as_str(), &queries, &mut documents, Some(doc)).unwrap();
-    res
+/// Whether a location step matches only direct children of the current node-set (a single
+/// `/`) or any descendant at any depth (`//`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Child,
+    Descendant,
 }
 
+/// One `/`- or `//`-separated location step, e.g. the `para[2]` in `/doc/section1/para[2]`:
+/// which axis to search from the current node-set, the tag name to match (`*` for any),
+/// and its predicates.
+#[derive(Debug, Clone)]
+struct Step {
+    axis: Axis,
+    name_test: String,
+    predicates: Vec<Predicate>,
+}
 
-fn execute_query(
-    xpath: &str,
-    queries: &xee_xpath::Queries<'_>,
-    documents: &mut xee_xpath::Documents,
-    doc: Option<xee_xpath::DocumentHandle>,
-) -> Result<XpathResult, anyhow::Error> {
+/// Parse a predicate's inner text (without the surrounding `[` `]`) into a [`Predicate`].
+fn parse_predicate(inner: &str) -> Option<Predicate> {
+    let inner = inner.trim();
+    if inner == "last()" {
+        return Some(Predicate::Last);
+    }
+    if let Ok(position) = inner.parse::<usize>() {
+        // `[n]` is 1-based; `0` (and anything that would underflow the `position - 1`
+        // conversion to a node-set index in `apply_predicate`) never matches anything, so
+        // drop it the same way an unparseable predicate is dropped rather than panicking.
+        return if position == 0 { None } else { Some(Predicate::Position(position)) };
+    }
+    let rest = inner.strip_prefix('@')?;
+    let (attr, value) = rest.split_once('=')?;
+    let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+    Some(Predicate::AttrEquals(attr.trim().to_string(), value.to_string()))
+}
 
-    let no_result = XpathResult::new(0, vec!["".to_string()]);
+/// Parse one location step's text (e.g. `para[2]`, `heading[@type="myHeading"]`, `*`) into
+/// its name test and predicates; the axis comes from the `/` vs `//` separator that
+/// preceded it, determined by the caller.
+fn parse_step(axis: Axis, text: &str) -> Step {
+    let bracket_pos = text.find('[');
+    let name_test = match bracket_pos {
+        Some(pos) => &text[..pos],
+        None => text,
+    };
 
-    let sequence_query = queries.sequence(xpath);
-    let sequence_query = match sequence_query {
-        Ok(sequence_query) => sequence_query,
-        Err(e) => {
-            render_error(xpath, e);
-            return Ok(no_result);
-        }
+    let mut predicates = Vec::new();
+    let mut rest = match bracket_pos {
+        Some(pos) => &text[pos..],
+        None => "",
     };
-    let mut context_builder = sequence_query.dynamic_context_builder(documents);
-    if let Some(doc) = doc {
-        context_builder.context_item(doc.to_item(documents)?);
-    }
-    let context = context_builder.build();
 
-    let sequence = sequence_query.execute_with_context(documents, &context);
-    let sequence = match sequence {
-        Ok(sequence) => sequence,
-        Err(e) => {
-            render_error(xpath, e);
-            return Ok(no_result);
+    while let Some(stripped) = rest.strip_prefix('[') {
+        let Some(end) = stripped.find(']') else { break };
+        if let Some(predicate) = parse_predicate(&stripped[..end]) {
+            predicates.push(predicate);
         }
-    };
+        rest = &stripped[end + 1..];
+    }
 
-    println!(
-        "No of items found: {}\n{}", sequence.len(),
-        sequence.display_representation(documents.xot(), &context)
-    );
+    Step { axis, name_test: name_test.to_string(), predicates }
+}
 
-    let mut results: Vec<String> = Vec::new();
-    for idx in 0..sequence.len() {
-        results.push(sequence.get(idx).unwrap().display_representation(documents.xot(), &context).unwrap());
-        println!("{}", sequence.get(idx).unwrap().display_representation(documents.xot(), &context).unwrap());
+/// Split an xpath into its location steps, tracking the `//` descendant-axis marker (an
+/// empty segment between two `/`s) as it goes.
+fn parse_location_steps(xpath: &str) -> Vec<Step> {
+    let mut steps = Vec::new();
+    let mut pending_axis = Axis::Child;
+    let mut first = true;
+
+    for token in xpath.split('/') {
+        if token.is_empty() {
+            if !first {
+                pending_axis = Axis::Descendant;
+            }
+            first = false;
+            continue;
+        }
+        first = false;
+        steps.push(parse_step(pending_axis, token));
+        pending_axis = Axis::Child;
     }
 
-    // construct the result
-    let result = XpathResult::new(sequence.len(),results);
+    steps
+}
 
-    Ok(result)
+fn name_matches(node: Node, name_test: &str) -> bool {
+    name_test == "*" || node.tag_name().name() == name_test
 }
 
+/// Every element matching `name_test` reachable from `current` via `axis`.
+fn step_candidates<'a, 'input>(current: &[Node<'a, 'input>], axis: Axis, name_test: &str) -> Vec<Node<'a, 'input>> {
+    let mut candidates = Vec::new();
+    for node in current {
+        let reachable: Box<dyn Iterator<Item = Node<'a, 'input>>> = match axis {
+            Axis::Child => Box::new(node.children()),
+            Axis::Descendant => Box::new(node.descendants()),
+        };
+        candidates.extend(reachable.filter(|n| n.is_element() && name_matches(*n, name_test)));
+    }
+    candidates
+}
 
-fn make_static_context_builder<'a>(
-    default_namespace_uri: Option<&'a str>,
-    namespaces: &'a [String],
-) -> anyhow::Result<StaticContextBuilder<'a>> {
-    let mut static_context_builder = xee_xpath::context::StaticContextBuilder::default();
-    if let Some(default_namespace_uri) = default_namespace_uri {
-        static_context_builder.default_element_namespace(default_namespace_uri);
-    }
-    let namespaces = namespaces
-        .iter()
-        .map(|declaration| {
-            let mut parts = declaration.splitn(2, '=');
-            let prefix = parts.next().ok_or(anyhow::anyhow!("missing prefix"))?;
-            let uri = parts.next().ok_or(anyhow::anyhow!("missing uri"))?;
-            Ok((prefix, uri))
-        })
-        .collect::<Result<Vec<_>, anyhow::Error>>()?;
-
-    static_context_builder.namespaces(namespaces);
-    Ok(static_context_builder)
+/// Every element sharing `node`'s tag name under `node`'s parent, in document order - the
+/// node-set `position()`/`last()` are evaluated against.
+fn same_name_siblings<'a, 'input>(node: Node<'a, 'input>) -> Vec<Node<'a, 'input>> {
+    match node.parent() {
+        Some(parent) => parent.children().filter(|c| c.is_element() && c.tag_name() == node.tag_name()).collect(),
+        None => vec![node],
+    }
 }
 
+fn apply_predicate<'a, 'input>(candidates: Vec<Node<'a, 'input>>, predicate: &Predicate) -> Vec<Node<'a, 'input>> {
+    match predicate {
+        Predicate::AttrEquals(attr, value) => candidates
+            .into_iter()
+            .filter(|node| node.attribute(attr.as_str()) == Some(value.as_str()))
+            .collect(),
+        Predicate::Position(position) => candidates
+            .into_iter()
+            .filter(|node| same_name_siblings(*node).iter().position(|sibling| *sibling == *node) == Some(position - 1))
+            .collect(),
+        Predicate::Last => candidates
+            .into_iter()
+            .filter(|node| same_name_siblings(*node).last() == Some(node))
+            .collect(),
+    }
+}
 
+fn evaluate_step<'a, 'input>(current: &[Node<'a, 'input>], step: &Step) -> Vec<Node<'a, 'input>> {
+    let mut candidates = step_candidates(current, step.axis, &step.name_test);
+    for predicate in &step.predicates {
+        candidates = apply_predicate(candidates, predicate);
+    }
+    candidates
+}
 
+/// Serialize a node (and its children) back into XML text, the way the original
+/// `xee_xpath`-backed implementation rendered each result item.
+fn serialize_node(node: Node) -> String {
+    if node.is_text() {
+        return node.text().unwrap_or("").to_string();
+    }
+    if !node.is_element() {
+        return String::new();
+    }
 
-fn render_error(src: &str, e: Error) {
-    let red = ariadne::Color::Red;
+    let tag = node.tag_name().name();
+    let mut out = format!("<{}", tag);
+    for attr in node.attributes() {
+        out.push_str(&format!(" {}=\"{}\"", attr.name(), attr.value()));
+    }
+    out.push('>');
+    for child in node.children() {
+        out.push_str(&serialize_node(child));
+    }
+    out.push_str(&format!("</{}>", tag));
+    out
+}
 
-    let mut report = ariadne::Report::build(ariadne::ReportKind::Error, ("source", (0..0)))
-        .with_code(e.error.code());
+/// Evaluate `expr` - an XPath 1.0 subset supporting the child (`/`) and descendant (`//`)
+/// axes, the `*` node wildcard, positional predicates (`para[2]`, `para[last()]`), and
+/// attribute-equality predicates (`heading[@type="myHeading"]`) - against the in-memory XML
+/// document `srcxml`, returning every matching node serialized back to XML text.
+pub fn eval_xpath(expr: String, srcxml: String) -> XpathResult {
+    let doc = match Document::parse(&srcxml) {
+        Ok(doc) => doc,
+        Err(e) => {
+            log::error!("Failed to parse XML for xpath evaluation: {}", e);
+            return XpathResult::new(0, vec!["".to_string()]);
+        }
+    };
 
-    if let Some(span) = e.span {
-        report = report.with_label(
-            ariadne::Label::new(("source", span.range()))
-                .with_message(e.error.message())
-                .with_color(red),
-        )
+    let steps = parse_location_steps(expr.trim());
+    let mut current = vec![doc.root()];
+    for step in &steps {
+        current = evaluate_step(&current, step);
+        if current.is_empty() {
+            break;
+        }
     }
-    report
-        .finish()
-        .eprint(("source", ariadne::Source::from(src)))
-        .unwrap();
-    println!("{}", e.error.note());
-}
\ No newline at end of file
+
+    let result: Vec<String> = current.iter().map(|node| serialize_node(*node)).collect();
+    XpathResult::new(result.len(), result)
+}
@@ -1,100 +1,271 @@
+use std::collections::VecDeque;
 
 use roxmltree::{Document, Node};
 
-/// Check if an attribute uniquely identifies the node among all nodes in the document.
-fn is_attribute_unique(doc: &Document, node: Node, attr_name: &str) -> bool {
-    if let Some(attr_value) = node.attribute(attr_name) {
-        let count = doc
-            .descendants()
-            .filter(|n| n.attribute(attr_name) == Some(attr_value))
-            .count();
-        return count == 1;
+/// A single `[@attr='value']` predicate attached to a candidate's step.
+#[derive(Debug, Clone, PartialEq)]
+struct Predicate {
+    attr: String,
+    value: String,
+}
+
+/// One step of a candidate XPath, e.g. the `Button[@Name='Save'][2]` in `//Pane/Button[@Name='Save'][2]`.
+#[derive(Debug, Clone)]
+struct Step {
+    tag: String,
+    predicates: Vec<Predicate>,
+    position: Option<usize>,
+}
+
+impl Step {
+    fn wildcard() -> Self {
+        Step { tag: "*".to_string(), predicates: Vec::new(), position: None }
+    }
+
+    fn to_xpath_part(&self) -> String {
+        let mut part = self.tag.clone();
+        for predicate in &self.predicates {
+            part.push_str(&format!("[@{}='{}']", predicate.attr, predicate.value));
+        }
+        if let Some(position) = self.position {
+            part.push_str(&format!("[{}]", position));
+        }
+        part
+    }
+}
+
+/// A candidate locator under construction: a chain of steps anchored by a leading `//`,
+/// read left (outermost ancestor considered so far) to right (the target node itself).
+#[derive(Debug, Clone)]
+struct Candidate {
+    steps: Vec<Step>,
+}
+
+impl Candidate {
+    fn seed() -> Self {
+        Candidate { steps: vec![Step::wildcard()] }
+    }
+
+    fn to_xpath(&self) -> String {
+        format!("//{}", self.steps.iter().map(Step::to_xpath_part).collect::<Vec<_>>().join("/"))
+    }
+}
+
+/// Attribute priority list, blacklist, and length cutoff controlling how ROBULA+ grows
+/// and ranks candidate locators, tunable by the caller for a given app's attribute
+/// conventions.
+#[derive(Debug, Clone)]
+pub struct RobulaConfig {
+    /// Attributes tried (in order) when adding a predicate to a step; each one present
+    /// on the target node under consideration yields its own candidate.
+    pub attribute_priority: Vec<String>,
+    /// Attributes that are never turned into predicates - runtime ids, coordinates, and
+    /// anything else that wouldn't be stable across a second run.
+    pub attribute_blacklist: Vec<String>,
+    /// Candidates with this many steps are still checked but not expanded further.
+    pub max_length: usize,
+}
+
+impl Default for RobulaConfig {
+    fn default() -> Self {
+        RobulaConfig {
+            attribute_priority: vec![
+                "id".to_string(),
+                "name".to_string(),
+                "AutomationId".to_string(),
+                "ClassName".to_string(),
+                "Name".to_string(),
+            ],
+            attribute_blacklist: vec!["RtID".to_string(), "x".to_string(), "y".to_string()],
+            max_length: 32,
+        }
     }
-    false
 }
 
-/// Generate a robust, ROBULA+-like XPath for the given node.
-fn get_xpath_robula(doc: &Document, node: Node) -> String {
-    // Rule 1: Prefer globally unique attribute
-    for attr in ["id", "name"] {
-        if is_attribute_unique(doc, node, attr) {
-            return format!("//*[@{}='{}']", attr, node.attribute(attr).unwrap());
+fn tag_matches(node: Node, tag: &str) -> bool {
+    tag == "*" || node.tag_name().name() == tag
+}
+
+fn step_matches(node: Node, step: &Step) -> bool {
+    if !tag_matches(node, &step.tag) {
+        return false;
+    }
+
+    for predicate in &step.predicates {
+        if node.attribute(predicate.attr.as_str()) != Some(predicate.value.as_str()) {
+            return false;
+        }
+    }
+
+    if let Some(position) = step.position {
+        let Some(parent) = node.parent() else { return false };
+        let index = parent.children().filter(|c| c.is_element() && tag_matches(*c, &step.tag)).position(|c| c == node);
+        if index != Some(position - 1) {
+            return false;
         }
     }
 
-    // Build full path up to the root with optimization rules
-    let mut path_parts = Vec::new();
-    let mut current = Some(node);
+    true
+}
 
-    while let Some(n) = current {
-        if n.is_element() {
-            let tag = n.tag_name().name();
+/// Every element in the document selected by `candidate`: the first step matches any
+/// descendant of the document (mirroring the leading `//`), every following step must be
+/// a direct child of a node matched by the previous step.
+fn select<'a, 'input>(doc: &'a Document<'input>, candidate: &Candidate) -> Vec<Node<'a, 'input>> {
+    let Some(first_step) = candidate.steps.first() else { return Vec::new() };
 
-            // Try using unique attribute in parent scope
-            if is_attribute_unique(doc, n, "name") {
-                path_parts.push(format!("{}[@name='{}']", tag, n.attribute("name").unwrap()));
-                break;
-            }
+    let mut current: Vec<Node> = doc.descendants().filter(|n| n.is_element() && step_matches(*n, first_step)).collect();
 
-            // Determine if this node needs an index
-            let parent = n.parent();
-            let same_tag_count = parent.map_or(1, |p| {
-                p.children()
-                    .filter(|c| c.is_element() && c.tag_name().name() == tag)
-                    .count()
-            });
-
-            if same_tag_count > 1 {
-                // Count this node's position among siblings
-                let mut index = 1;
-                let mut prev = n.prev_sibling();
-                while let Some(sib) = prev {
-                    if sib.is_element() && sib.tag_name().name() == tag {
-                        index += 1;
-                    }
-                    prev = sib.prev_sibling();
-                }
-                path_parts.push(format!("{}[{}]", tag, index));
-            } else {
-                path_parts.push(tag.to_string());
+    for step in &candidate.steps[1..] {
+        let mut next = Vec::new();
+        for node in &current {
+            for child in node.children().filter(|c| c.is_element() && step_matches(*c, step)) {
+                next.push(child);
             }
         }
-        current = n.parent();
+        current = next;
     }
 
-    path_parts.reverse();
-    format!("/{}", path_parts.join("/"))
+    current
+}
+
+fn candidate_matches_target(doc: &Document, candidate: &Candidate, target: Node) -> bool {
+    select(doc, candidate).iter().any(|n| *n == target)
 }
 
+/// Apply every ROBULA+ transformation (in priority order) to `candidate`'s last step
+/// (the one representing the target node) plus, as `transfAddLevel`, one that grows the
+/// candidate by an extra ancestor step. Only candidates that still select `target` are
+/// returned.
+fn expand(doc: &Document, candidate: &Candidate, target: Node, config: &RobulaConfig) -> Vec<Candidate> {
+    let mut out = Vec::new();
+    let last = candidate.steps.len() - 1;
+    let last_step = &candidate.steps[last];
 
+    // (1) transfConvertStar: replace the target step's `*` with the target's own tag name.
+    if last_step.tag == "*" {
+        let mut c = candidate.clone();
+        c.steps[last].tag = target.tag_name().name().to_string();
+        if candidate_matches_target(doc, &c, target) {
+            out.push(c);
+        }
+    }
 
-// pub fn get_xpath_from_runtime_id(runtime_id: String, xml: &str) -> String {
+    // (2) transfAddId / transfAddAttribute: append one `[@attr='value']` predicate,
+    // walking the priority list and skipping blacklisted/volatile attributes.
+    let usable_attrs: Vec<String> = config
+        .attribute_priority
+        .iter()
+        .filter(|attr| !config.attribute_blacklist.contains(attr))
+        .filter(|attr| target.attribute(attr.as_str()).is_some())
+        .filter(|attr| !last_step.predicates.iter().any(|p| p.attr == **attr))
+        .cloned()
+        .collect();
 
-//     let root_node = RNode::new_document();
-//     parse(root_node.clone(), xml, None).unwrap();
+    for attr in &usable_attrs {
+        let mut c = candidate.clone();
+        let value = target.attribute(attr.as_str()).unwrap().to_string();
+        c.steps[last].predicates.push(Predicate { attr: attr.clone(), value });
+        if candidate_matches_target(doc, &c, target) {
+            out.push(c);
+        }
+    }
 
-//     let target = root_node
-//     .descend_iter()
-//     .find(|n| n.attribute("RtID") == Some(runtime_id.clone()))
-//     .unwrap();
+    // (3) transfAddAttributeSet: combine two attribute predicates on the same step.
+    for i in 0..usable_attrs.len() {
+        for j in (i + 1)..usable_attrs.len() {
+            let mut c = candidate.clone();
+            for attr in [&usable_attrs[i], &usable_attrs[j]] {
+                let value = target.attribute(attr.as_str()).unwrap().to_string();
+                c.steps[last].predicates.push(Predicate { attr: attr.clone(), value });
+            }
+            if candidate_matches_target(doc, &c, target) {
+                out.push(c);
+            }
+        }
+    }
 
-//     get_xpath_from_rnode(&root_node, &target)
+    // (4) transfAddPosition: append a `[n]` positional index among same-test siblings.
+    if last_step.position.is_none() {
+        if let Some(parent) = target.parent() {
+            let index = parent.children().filter(|c| c.is_element() && tag_matches(*c, &last_step.tag)).position(|c| c == target);
+            if let Some(index) = index {
+                let mut c = candidate.clone();
+                c.steps[last].position = Some(index + 1);
+                if candidate_matches_target(doc, &c, target) {
+                    out.push(c);
+                }
+            }
+        }
+    }
 
-// }
+    // (5) transfAddLevel: prepend one more ancestor step (`//*/...`).
+    let mut c = candidate.clone();
+    c.steps.insert(0, Step::wildcard());
+    if candidate_matches_target(doc, &c, target) {
+        out.push(c);
+    }
 
-pub fn get_xpath_full_from_runtime_id(runtime_id: &str, xml: &str) -> String {
+    out
+}
 
-    let doc = Document::parse(xml).unwrap();
+/// Full iterative ROBULA+ search: maintain a worklist of candidate XPaths seeded with the
+/// most generic expression `//*`, repeatedly popping a candidate and - if it still
+/// matches `target` but isn't unique yet - expanding it with every transformation that
+/// keeps it matching, until a candidate selects `target` and nothing else.
+fn robula_search(doc: &Document, target: Node, config: &RobulaConfig) -> String {
+    let mut worklist: VecDeque<Candidate> = VecDeque::new();
+    worklist.push_back(Candidate::seed());
+
+    // Falls back to the best (fewest-matches, then shortest) still-matching candidate
+    // seen if the search space is exhausted before anything becomes unique - should only
+    // happen if `target` shares literally every attribute with a sibling at every
+    // ancestor level. BFS always visits the generic seed `//*` first, which matches
+    // everything, so this has to keep updating as narrower candidates turn up rather than
+    // locking onto whichever candidate happened to be first.
+    let mut fallback: Option<Candidate> = None;
+    let mut fallback_match_count = usize::MAX;
+
+    while let Some(candidate) = worklist.pop_front() {
+        let matches = select(doc, &candidate);
+        if !matches.iter().any(|n| *n == target) {
+            continue;
+        }
+
+        if matches.len() == 1 {
+            return candidate.to_xpath();
+        }
+
+        let is_better = match matches.len().cmp(&fallback_match_count) {
+            std::cmp::Ordering::Less => true,
+            std::cmp::Ordering::Equal => fallback.as_ref().is_some_and(|f| candidate.steps.len() < f.steps.len()),
+            std::cmp::Ordering::Greater => false,
+        };
+        if is_better {
+            fallback = Some(candidate.clone());
+            fallback_match_count = matches.len();
+        }
 
-    if let Some(node_id) = doc
-        .descendants()
-        .find(|n| n.attribute("RtID") == Some(runtime_id)) {
-            get_xpath_robula(&doc, node_id)
-        } else {
-            "UI Element not found - no xpath available".to_string()
+        if candidate.steps.len() >= config.max_length {
+            continue;
         }
 
+        for expanded in expand(doc, &candidate, target, config) {
+            worklist.push_back(expanded);
+        }
+    }
+
+    fallback.map(|c| c.to_xpath()).unwrap_or_else(|| "UI Element not found - no xpath available".to_string())
+}
 
-    
+/// Resolve the node carrying `RtID="{runtime_id}"` in `xml` and generate a robust, short
+/// locator for it via the full ROBULA+ search (see [`RobulaConfig`] to tune attribute
+/// priority/blacklist/length).
+pub fn get_xpath_full_from_runtime_id(runtime_id: &str, xml: &str) -> String {
+    let doc = Document::parse(xml).unwrap();
 
-}
\ No newline at end of file
+    match doc.descendants().find(|n| n.attribute("RtID") == Some(runtime_id)) {
+        Some(node) => robula_search(&doc, node, &RobulaConfig::default()),
+        None => "UI Element not found - no xpath available".to_string(),
+    }
+}
@@ -1,7 +1,13 @@
-use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN};
-use windows::Win32::Graphics::Gdi::{MONITOR_FROM_FLAGS, MonitorFromPoint};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN,
+    SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+};
+use windows::Win32::Graphics::Gdi::{
+    MONITOR_FROM_FLAGS, MonitorFromPoint, EnumDisplayMonitors, GetMonitorInfoW, HMONITOR, HDC,
+    MONITORINFO, MONITORINFOF_PRIMARY,
+};
 use windows::Win32::UI::HiDpi::{DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE, MONITOR_DPI_TYPE, GetDpiForMonitor, SetProcessDpiAwarenessContext, GetDpiAwarenessContextForProcess, GetAwarenessFromDpiAwarenessContext}; //DPI_AWARENESS, DPI_AWARENESS_CONTEXT, GetThreadDpiAwarenessContext
-use windows::Win32::Foundation::{POINT, HANDLE};
+use windows::Win32::Foundation::{POINT, HANDLE, RECT, LPARAM, BOOL};
 
 use pyo3::prelude::*;
 
@@ -12,6 +18,67 @@ struct ScreenSize {
     height: i32,
 }
 
+/// One physical display: its device handle, monitor-space bounds, work area (bounds minus
+/// taskbar/docked toolbars), and its own per-monitor DPI scale - the unit `ScreenContext`'s
+/// monitor-enumeration methods return, so callers on a mixed-DPI multi-head setup can tell
+/// which display a coordinate belongs to and scale accordingly.
+#[pyclass]
+#[derive(Debug, Clone, Copy)]
+pub struct Monitor {
+    handle: isize,
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+    work_left: i32,
+    work_top: i32,
+    work_right: i32,
+    work_bottom: i32,
+    scale: f32,
+    primary: bool,
+}
+
+#[pymethods]
+impl Monitor {
+    pub fn __repr__(&self) -> PyResult<String> {
+        PyResult::Ok(format!(
+            "<Monitor handle={} bounds=({}, {}, {}, {}) work_area=({}, {}, {}, {}) scale={} primary={}>",
+            self.handle, self.left, self.top, self.right, self.bottom,
+            self.work_left, self.work_top, self.work_right, self.work_bottom,
+            self.scale, self.primary,
+        ))
+    }
+
+    pub fn __str__(&self) -> PyResult<String> {
+        self.__repr__()
+    }
+
+    pub fn get_handle(&self) -> isize {
+        self.handle
+    }
+
+    /// This monitor's bounds in virtual-desktop coordinates, as `(left, top, right, bottom)`.
+    pub fn get_bounds(&self) -> (i32, i32, i32, i32) {
+        (self.left, self.top, self.right, self.bottom)
+    }
+
+    /// This monitor's work area (bounds minus the taskbar and any docked toolbars), as
+    /// `(left, top, right, bottom)`.
+    pub fn get_work_area(&self) -> (i32, i32, i32, i32) {
+        (self.work_left, self.work_top, self.work_right, self.work_bottom)
+    }
+
+    /// This monitor's own DPI scale factor (its DPI divided by 96), independent of every
+    /// other monitor's scale.
+    pub fn get_scale(&self) -> f32 {
+        self.scale
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+}
+
 #[pyclass]
 #[derive(Debug)]
 // #[repr(C)]
@@ -28,7 +95,7 @@ impl ScreenContext {
 
         let screen_size = get_system_metrics();
         let screen_width = screen_size.width;
-        let screen_height = screen_size.height; 
+        let screen_height = screen_size.height;
         let screen_scale = get_screen_scale_factor();
 
         Self {
@@ -36,7 +103,7 @@ impl ScreenContext {
             screen_height,
             screen_scale,
         }
-    }    
+    }
 
     pub fn __repr__(&self) -> PyResult<String> {
         PyResult::Ok(format!("<ScreenContext screen_width={} screen_height={} screen_scale={}>", self.screen_width, self.screen_height, self.screen_scale))
@@ -58,6 +125,41 @@ impl ScreenContext {
         self.screen_scale
     }
 
+    /// Every display currently attached, primary first, each with its own bounds, work
+    /// area, and DPI scale - the per-monitor-aware replacement for the single averaged
+    /// `screen_scale` above.
+    pub fn get_monitors(&self) -> Vec<Monitor> {
+        let mut monitors: Vec<Monitor> = enumerate_monitor_handles().into_iter().map(monitor_from_handle).collect();
+        monitors.sort_by_key(|m| !m.primary);
+        monitors
+    }
+
+    /// The monitor Windows considers primary, if any are attached.
+    pub fn get_primary_monitor(&self) -> Option<Monitor> {
+        self.get_monitors().into_iter().find(|m| m.primary)
+    }
+
+    /// The bounding box of the whole virtual desktop (the union of every monitor's
+    /// bounds), as `(left, top, right, bottom)`.
+    pub fn get_virtual_desktop_bounds(&self) -> (i32, i32, i32, i32) {
+        unsafe {
+            let left = GetSystemMetrics(SM_XVIRTUALSCREEN);
+            let top = GetSystemMetrics(SM_YVIRTUALSCREEN);
+            let width = GetSystemMetrics(SM_CXVIRTUALSCREEN);
+            let height = GetSystemMetrics(SM_CYVIRTUALSCREEN);
+            (left, top, left + width, top + height)
+        }
+    }
+
+    /// The monitor that contains (or is nearest to) the virtual-desktop point `(x, y)`,
+    /// so a caller can look up the right DPI scale for an arbitrary coordinate.
+    pub fn monitor_from_point(&self, x: i32, y: i32) -> Monitor {
+        unsafe {
+            let hmonitor = MonitorFromPoint(POINT { x, y }, MONITOR_FROM_FLAGS { 0: 2 }); // MONITOR_DEFAULTTONEAREST
+            monitor_from_handle(hmonitor)
+        }
+    }
+
 }
 
 fn get_system_metrics() -> ScreenSize {
@@ -91,4 +193,49 @@ fn get_screen_scale_factor() -> f32 {
     }
 
 
-}
\ No newline at end of file
+}
+
+/// Build a [`Monitor`] from its Win32 handle via `GetMonitorInfoW` (bounds, work area,
+/// primary flag) and `GetDpiForMonitor` (this monitor's own DPI scale).
+fn monitor_from_handle(hmonitor: HMONITOR) -> Monitor {
+    unsafe {
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        let _res = GetMonitorInfoW(hmonitor, &mut info);
+
+        let mut dpi_x = 0;
+        let mut dpi_y = 0;
+        let _res = GetDpiForMonitor(hmonitor, MONITOR_DPI_TYPE { 0: 0 }, &mut dpi_x, &mut dpi_y);
+
+        Monitor {
+            handle: hmonitor.0 as isize,
+            left: info.rcMonitor.left,
+            top: info.rcMonitor.top,
+            right: info.rcMonitor.right,
+            bottom: info.rcMonitor.bottom,
+            work_left: info.rcWork.left,
+            work_top: info.rcWork.top,
+            work_right: info.rcWork.right,
+            work_bottom: info.rcWork.bottom,
+            scale: dpi_x as f32 / 96.0,
+            primary: (info.dwFlags & MONITORINFOF_PRIMARY) != 0,
+        }
+    }
+}
+
+unsafe extern "system" fn enum_monitor_proc(hmonitor: HMONITOR, _hdc: HDC, _rect: *mut RECT, lparam: LPARAM) -> BOOL {
+    let monitors = &mut *(lparam.0 as *mut Vec<HMONITOR>);
+    monitors.push(hmonitor);
+    BOOL(1)
+}
+
+/// Every monitor handle currently attached, via `EnumDisplayMonitors`.
+fn enumerate_monitor_handles() -> Vec<HMONITOR> {
+    let mut monitors: Vec<HMONITOR> = Vec::new();
+    unsafe {
+        let _res = EnumDisplayMonitors(HDC(std::ptr::null_mut()), None, Some(enum_monitor_proc), LPARAM(&mut monitors as *mut _ as isize));
+    }
+    monitors
+}
@@ -21,17 +21,13 @@ static INIT: Once = Once::new();
 /// Initialize logging and other global resources
 fn init_bromium() {
     INIT.call_once(|| {
-        // Initialize logging system
+        // Initialize logging system; retention of old rotated files is now handled
+        // internally by `init_logging` via `LoggingConfig::retention_days`.
         if let Err(e) = logging::init_logging() {
             eprintln!("Failed to initialize logging: {}", e);
         }
-        
-        // Clean up old log files (keep last 10)
-        if let Err(e) = logging::cleanup_old_logs(10) {
-            log::warn!("Failed to clean up old log files: {}", e);
-        }
-        
-        log::info!("Bromium library initialized successfully");
+
+        tracing::info!("Bromium library initialized successfully");
     });
 }
 
@@ -44,6 +40,7 @@ fn bromium(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<windriver::WinDriver>()?;
     m.add_class::<windriver::Element>()?;
     m.add_class::<context::ScreenContext>()?;
+    m.add_class::<context::Monitor>()?;
     
     log::info!("Bromium Python module loaded");
     Ok(())
@@ -1,82 +1,97 @@
-use std::fs::{File, OpenOptions};
-use std::io::Write;
 use std::path::PathBuf;
-use std::sync::Mutex;
-use chrono::Local;
-use log::Record;
-use env_logger::fmt::Formatter;
-use lazy_static::lazy_static;
-
-// Global logger instance
-lazy_static! {
-    static ref LOG_FILE: Mutex<Option<File>> = Mutex::new(None);
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use tracing::Level;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::writer::MakeWriterExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Keeps the non-blocking file writer's background flush thread alive for the life of
+/// the process - the `tracing` equivalent of the old `LOG_FILE: Mutex<Option<File>>`
+/// handle that had to outlive every log call.
+static LOG_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+/// How long rotated log files are kept around and whether a structured JSON file is
+/// written alongside the human-readable one. Replaces the old `cleanup_old_logs(keep_count)`
+/// call that callers had to remember to invoke themselves.
+#[derive(Debug, Clone, Copy)]
+pub struct LoggingConfig {
+    pub retention_days: u32,
+    pub json: bool,
 }
 
-/// Initialize the logging system with file output
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        LoggingConfig {
+            retention_days: 10,
+            json: false,
+        }
+    }
+}
+
+/// Initialize the logging system with console output plus a daily-rotating file, using
+/// [`LoggingConfig::default`].
 pub fn init_logging() -> Result<(), Box<dyn std::error::Error>> {
-    // Create logs directory if it doesn't exist
+    init_logging_with(LoggingConfig::default())
+}
+
+/// Initialize the logging system on top of `tracing`/`tracing-subscriber`: a
+/// human-readable console layer, a file layer backed by a daily rolling appender (so
+/// rotation happens automatically instead of us managing one ever-growing file), and -
+/// when `config.json` is set - a second rolling appender emitting one structured JSON
+/// record per line for downstream tooling to parse.
+pub fn init_logging_with(config: LoggingConfig) -> Result<(), Box<dyn std::error::Error>> {
+    // Bridge plain `log::` call sites (still used in a few modules) into the same
+    // tracing subscriber, so they show up nested under whatever span is active.
+    let _ = tracing_log::LogTracer::init();
+
     let log_dir = get_log_directory()?;
     std::fs::create_dir_all(&log_dir)?;
-    
-    // Generate log file name with timestamp
-    let log_file_path = log_dir.join(format!("bromium_{}.log", 
-        chrono::Local::now().format("%Y%m%d_%H%M%S")));
-    
-    // Open log file
-    let file = OpenOptions::new()
-        .create(true)
-        .write(true)
-        .append(true)
-        .open(&log_file_path)?;
-    
-    // Store file handle in global static
-    *LOG_FILE.lock().unwrap() = Some(file);
-    
-    // Configure env_logger with custom format
-    env_logger::Builder::from_default_env()
-        .format(move |buf: &mut Formatter, record: &Record| {
-            let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
-            
-            // Format: [timestamp] [LEVEL] [module::function:line] message
-            writeln!(
-                buf,
-                "[{}] [{}] [{}:{}] {}",
-                timestamp,
-                record.level(),  // Use level directly instead of styling
-                record.module_path().unwrap_or("unknown"),
-                record.line().unwrap_or(0),
-                record.args()
-            )?;
-            
-            // Also write to file
-            write_to_file(record)?;
-            
-            Ok(())
-        })
-        .filter_level(log::LevelFilter::Debug)
-        .init();
-    
-    log::info!("Bromium logging initialized. Log file: {:?}", log_file_path);
-    Ok(())
-}
 
-/// Write log entry to file
-fn write_to_file(record: &Record) -> Result<(), std::io::Error> {
-    if let Ok(mut log_file_guard) = LOG_FILE.lock() {
-        if let Some(ref mut file) = *log_file_guard {
-            let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
-            writeln!(
-                file,
-                "[{}] [{}] [{}:{}] {}",
-                timestamp,
-                record.level(),
-                record.module_path().unwrap_or("unknown"),
-                record.line().unwrap_or(0),
-                record.args()
-            )?;
-            file.flush()?;
-        }
+    let file_appender = RollingFileAppender::new(Rotation::DAILY, &log_dir, "bromium.log");
+    let (file_writer, guard) = tracing_appender::non_blocking(file_appender);
+    let _ = LOG_GUARD.set(guard);
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"));
+
+    let console_layer = tracing_subscriber::fmt::layer()
+        .with_target(true)
+        .with_writer(std::io::stdout);
+
+    let file_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_target(true)
+        .with_writer(file_writer.with_max_level(tracing::Level::TRACE));
+
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(console_layer)
+        .with(file_layer);
+
+    if config.json {
+        let json_appender = RollingFileAppender::new(Rotation::DAILY, &log_dir, "bromium.json.log");
+        let (json_writer, json_guard) = tracing_appender::non_blocking(json_appender);
+        // Leak the guard alongside the file one - both must live for the process, and
+        // `LOG_GUARD` only has room for one, so this one rides along inside a `Box`.
+        Box::leak(Box::new(json_guard));
+
+        let json_layer = tracing_subscriber::fmt::layer()
+            .json()
+            .with_ansi(false)
+            .with_writer(json_writer);
+
+        registry.with(json_layer).try_init()?;
+    } else {
+        registry.try_init()?;
     }
+
+    purge_logs_older_than(&log_dir, config.retention_days);
+
+    tracing::info!(log_dir = %log_dir.display(), retention_days = config.retention_days, "Bromium logging initialized");
     Ok(())
 }
 
@@ -92,7 +107,7 @@ fn get_log_directory() -> Result<PathBuf, Box<dyn std::error::Error>> {
             Ok(PathBuf::from("./logs"))
         }
     }
-    
+
     #[cfg(not(windows))]
     {
         // On other platforms, use ~/.local/share/bromium/logs
@@ -105,33 +120,87 @@ fn get_log_directory() -> Result<PathBuf, Box<dyn std::error::Error>> {
     }
 }
 
-/// Macro for logging XPath operations with context
+/// Remove rotated log files older than `retention_days`, now just a retention setting
+/// applied once at startup instead of a method callers had to remember to invoke.
+fn purge_logs_older_than(log_dir: &PathBuf, retention_days: u32) {
+    let Ok(entries) = std::fs::read_dir(log_dir) else {
+        return;
+    };
+
+    let cutoff = std::time::SystemTime::now()
+        .checked_sub(std::time::Duration::from_secs(u64::from(retention_days) * 24 * 60 * 60));
+    let Some(cutoff) = cutoff else {
+        return;
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let is_log = entry
+            .path()
+            .to_str()
+            .map(|p| p.contains("bromium.log") || p.contains("bromium.json.log"))
+            .unwrap_or(false);
+        if !is_log {
+            continue;
+        }
+
+        let modified = entry.metadata().and_then(|m| m.modified());
+        if let Ok(modified) = modified {
+            if modified < cutoff {
+                if let Err(e) = std::fs::remove_file(entry.path()) {
+                    tracing::warn!(path = ?entry.path(), error = %e, "Failed to remove old log file");
+                } else {
+                    tracing::info!(path = ?entry.path(), "Removed old log file past retention");
+                }
+            }
+        }
+    }
+}
+
+/// Macro for logging XPath operations with context, as structured `tracing` fields
+/// rather than a pre-formatted string.
 #[macro_export]
 macro_rules! log_xpath_operation {
     ($level:expr, $operation:expr, $context:expr, $($arg:tt)*) => {
-        log::log!($level, "[XPATH_{}] {} - {}", $operation, $context, format!($($arg)*));
+        match $level {
+            log::Level::Error => tracing::error!(operation = $operation, context = $context, message = %format!($($arg)*)),
+            log::Level::Warn => tracing::warn!(operation = $operation, context = $context, message = %format!($($arg)*)),
+            log::Level::Info => tracing::info!(operation = $operation, context = $context, message = %format!($($arg)*)),
+            log::Level::Debug => tracing::debug!(operation = $operation, context = $context, message = %format!($($arg)*)),
+            log::Level::Trace => tracing::trace!(operation = $operation, context = $context, message = %format!($($arg)*)),
+        }
     };
 }
 
-/// Macro for logging UI automation operations with context
+/// Macro for logging UI automation operations with context, as structured `tracing`
+/// fields rather than a pre-formatted string.
 #[macro_export]
 macro_rules! log_uiauto_operation {
     ($level:expr, $operation:expr, $element_info:expr, $($arg:tt)*) => {
-        log::log!($level, "[UIAUTO_{}] {} - {}", $operation, $element_info, format!($($arg)*));
+        match $level {
+            log::Level::Error => tracing::error!(operation = $operation, element = $element_info, message = %format!($($arg)*)),
+            log::Level::Warn => tracing::warn!(operation = $operation, element = $element_info, message = %format!($($arg)*)),
+            log::Level::Info => tracing::info!(operation = $operation, element = $element_info, message = %format!($($arg)*)),
+            log::Level::Debug => tracing::debug!(operation = $operation, element = $element_info, message = %format!($($arg)*)),
+            log::Level::Trace => tracing::trace!(operation = $operation, element = $element_info, message = %format!($($arg)*)),
+        }
     };
 }
 
-/// Performance timer for logging operation durations
+/// A `tracing` span covering one UI-automation/XPath operation: entered on `new`, and on
+/// `Drop` emits the elapsed time as a structured event so nested operations show up as a
+/// span tree instead of flat `[PERF]` debug lines.
 pub struct PerformanceTimer {
-    start_time: std::time::Instant,
+    _span_guard: tracing::span::EnteredSpan,
+    start_time: Instant,
     operation_name: String,
 }
 
 impl PerformanceTimer {
     pub fn new(operation_name: &str) -> Self {
-        log::debug!("[PERF] Starting operation: {}", operation_name);
+        let span = tracing::debug_span!("operation", name = %operation_name).entered();
         Self {
-            start_time: std::time::Instant::now(),
+            _span_guard: span,
+            start_time: Instant::now(),
             operation_name: operation_name.to_string(),
         }
     }
@@ -139,45 +208,7 @@ impl PerformanceTimer {
 
 impl Drop for PerformanceTimer {
     fn drop(&mut self) {
-        let duration = self.start_time.elapsed();
-        log::debug!("[PERF] Completed operation '{}' in {:.3}ms", 
-                   self.operation_name, duration.as_secs_f64() * 1000.0);
+        let elapsed_ms = self.start_time.elapsed().as_secs_f64() * 1000.0;
+        tracing::event!(Level::DEBUG, operation = %self.operation_name, elapsed_ms, "operation completed");
     }
 }
-
-/// Clean up old log files (keep only the last N files)
-pub fn cleanup_old_logs(keep_count: usize) -> Result<(), Box<dyn std::error::Error>> {
-    let log_dir = get_log_directory()?;
-    if !log_dir.exists() {
-        return Ok(());
-    }
-    
-    let mut log_files: Vec<_> = std::fs::read_dir(&log_dir)?
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| {
-            entry.path().extension()
-                .and_then(|ext| ext.to_str())
-                .map(|ext| ext == "log")
-                .unwrap_or(false)
-        })
-        .collect();
-    
-    // Sort by modification time (newest first)
-    log_files.sort_by_key(|entry| {
-        entry.metadata()
-            .and_then(|meta| meta.modified())
-            .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
-    });
-    log_files.reverse();
-    
-    // Remove old files
-    for old_file in log_files.iter().skip(keep_count) {
-        if let Err(e) = std::fs::remove_file(old_file.path()) {
-            log::warn!("Failed to remove old log file {:?}: {}", old_file.path(), e);
-        } else {
-            log::info!("Removed old log file: {:?}", old_file.path());
-        }
-    }
-    
-    Ok(())
-}
\ No newline at end of file
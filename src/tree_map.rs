@@ -1,7 +1,82 @@
-//! A generic tree structure with fast key-value lookup (not collision safe!)
+//! A generic tree structure with fast key-value lookup (collision safe: `name_to_index`
+//! keeps every node that shares a name, not just the last one inserted).
 #![allow(dead_code)]
 use crate::{UIHashMap, UIHashSet};
 
+/// Score `name` against `query` as an ordered, case-insensitive subsequence match,
+/// fzf-style: consecutive runs of matched characters, matches right after a separator
+/// (space, `_`, `/`) or a camelCase boundary, and a match at the very start are all
+/// rewarded, while each gap between matched runs and any leading unmatched characters
+/// are penalized. Returns `None` if `query` doesn't occur as a subsequence of `name` at
+/// all, or `(score, matched_offsets)` otherwise.
+fn fuzzy_score(name: &str, query: &str) -> Option<(i32, Vec<usize>)> {
+    let name_chars: Vec<char> = name.chars().collect();
+    let query_chars: Vec<char> = query.to_lowercase().chars().collect();
+
+    if query_chars.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let mut positions: Vec<usize> = Vec::with_capacity(query_chars.len());
+    let mut qi = 0;
+    let mut score: i32 = 0;
+    let mut consecutive_run: i32 = 0;
+
+    for (ni, &c) in name_chars.iter().enumerate() {
+        if qi >= query_chars.len() {
+            break;
+        }
+        if c.to_lowercase().next() != Some(query_chars[qi]) {
+            continue;
+        }
+
+        let mut char_score = 10;
+
+        if ni == 0 {
+            char_score += 15; // match at the very start of the name
+        } else {
+            let prev = name_chars[ni - 1];
+            if prev == ' ' || prev == '_' || prev == '/' {
+                char_score += 12; // match right after a separator
+            } else if prev.is_lowercase() && c.is_uppercase() {
+                char_score += 12; // match at a camelCase boundary
+            }
+        }
+
+        match positions.last() {
+            Some(&prev_pos) if ni == prev_pos + 1 => {
+                consecutive_run += 1;
+                char_score += 5 * consecutive_run; // reward consecutive runs
+            }
+            Some(&prev_pos) => {
+                score -= (ni - prev_pos - 1) as i32; // penalize the gap since the last match
+                consecutive_run = 0;
+            }
+            None => {}
+        }
+
+        positions.push(ni);
+        score += char_score;
+        qi += 1;
+    }
+
+    if qi < query_chars.len() {
+        return None; // query isn't a subsequence of name at all
+    }
+
+    if let Some(&first) = positions.first() {
+        score -= first as i32; // penalize leading unmatched characters
+    }
+
+    Some((score, positions))
+}
+
+/// Escape `"` and `\` in a node label so it can be embedded in a Graphviz DOT quoted
+/// string without breaking the surrounding syntax.
+fn escape_dot_label(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
 // A generic node in a UITreeMap
 #[derive(Debug, Clone)]
 pub struct UITreeNode<T> {
@@ -15,7 +90,7 @@ pub struct UITreeNode<T> {
 #[derive(Debug, Clone)]
 pub struct UITreeMap<T> {
     nodes: Vec<UITreeNode<T>>,
-    name_to_index: UIHashMap<String, usize>, // Name-to-index map for optional lookups
+    name_to_index: UIHashMap<String, Vec<usize>>, // Name-to-indices map; collision safe - a name can own several nodes
 }
 
 impl<T> UITreeMap<T> {
@@ -28,8 +103,8 @@ impl<T> UITreeMap<T> {
             data: root_data,
         };
 
-        let mut name_to_index = UIHashMap::default();
-        name_to_index.insert(root_name, 0);
+        let mut name_to_index: UIHashMap<String, Vec<usize>> = UIHashMap::default();
+        name_to_index.insert(root_name, vec![0]);
 
         Self {
             nodes: vec![root],
@@ -59,12 +134,166 @@ impl<T> UITreeMap<T> {
             data,
         };
 
-        self.name_to_index.insert(name.to_string(), index);
+        self.name_to_index.entry(name.to_string()).or_default().push(index);
         self.nodes[parent].children.push(index);
         self.nodes.push(node);
         index
     }
 
+    /// Every node index registered under `name`, in insertion order. Unlike a plain
+    /// `name_to_index` lookup this doesn't silently drop earlier nodes when a name is
+    /// reused - returns an empty slice if nothing is registered under `name`.
+    pub fn find_all(&self, name: &str) -> &[usize] {
+        self.name_to_index.get(name).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    /// Every node index for which `pred` returns `true`, in tree order. The flat-list
+    /// counterpart of [`Self::filter`], for callers that need a list rather than a pruned
+    /// tree.
+    pub fn find_by<F>(&self, pred: F) -> Vec<usize>
+    where
+        F: Fn(usize, &T) -> bool,
+    {
+        self.nodes.iter().filter(|node| pred(node.index, &node.data)).map(|node| node.index).collect()
+    }
+
+    /// Build a new tree containing every node for which `keep` returns `true`, plus all
+    /// of its ancestors (so retained nodes still resolve a correct root-to-node path via
+    /// `get_path_to_element`) - the way a file-explorer filter collapses a directory tree
+    /// down to matching entries while keeping the folders that lead to them.
+    pub fn filter<F>(&self, keep: F) -> UITreeMap<T>
+    where
+        T: Clone,
+        F: Fn(usize, &T) -> bool,
+    {
+        let mut keep_set: UIHashSet<usize> = UIHashSet::new();
+        keep_set.insert(0); // root always stays, so retained paths remain connected
+
+        for node in &self.nodes {
+            if !keep(node.index, &node.data) {
+                continue;
+            }
+
+            let mut current = node.index;
+            while keep_set.insert(current) && current != 0 {
+                current = self.nodes[current].parent;
+            }
+        }
+
+        let root_node = &self.nodes[0];
+        let mut new_tree = UITreeMap::new(root_node.name.clone(), root_node.data.clone());
+
+        let mut index_map: UIHashMap<usize, usize> = UIHashMap::default();
+        index_map.insert(0, 0);
+
+        let mut visited: UIHashSet<usize> = UIHashSet::new();
+        visited.insert(0);
+
+        let mut queue: std::collections::VecDeque<usize> = std::collections::VecDeque::new();
+        queue.push_back(0);
+
+        while let Some(old_index) = queue.pop_front() {
+            let new_parent = index_map[&old_index];
+            for &old_child in &self.nodes[old_index].children {
+                if !keep_set.contains(&old_child) || !visited.insert(old_child) {
+                    continue;
+                }
+
+                let child_node = &self.nodes[old_child];
+                let new_index = new_tree.add_child(new_parent, &child_node.name, child_node.data.clone());
+                index_map.insert(old_child, new_index);
+                queue.push_back(old_child);
+            }
+        }
+
+        new_tree
+    }
+
+    /// Serialize the tree rooted at `index` to a nested JSON document of the shape
+    /// `{name, index, data, children: [...]}`, with `data` produced by `display` - the
+    /// JSON analogue of [`Self::debug_tree`], for handing a tree to an external
+    /// visualizer or diffing two snapshots instead of only printing one to stdout.
+    pub fn to_json(&self, index: usize, display: &impl Fn(&T) -> serde_json::Value) -> serde_json::Value {
+        let mut visited = UIHashSet::new();
+        self.to_json_node(index, display, &mut visited)
+    }
+
+    fn to_json_node(&self, index: usize, display: &impl Fn(&T) -> serde_json::Value, visited: &mut UIHashSet<usize>) -> serde_json::Value {
+        if !visited.insert(index) {
+            return serde_json::json!({ "cycle": index });
+        }
+
+        let node = &self.nodes[index];
+        let children: Vec<serde_json::Value> = node.children.iter().map(|&child| self.to_json_node(child, display, visited)).collect();
+
+        serde_json::json!({
+            "name": node.name,
+            "index": node.index,
+            "data": display(&node.data),
+            "children": children,
+        })
+    }
+
+    /// Render the tree rooted at `index` as a Graphviz DOT digraph - one node per tree
+    /// node labelled by `label`, parent-to-child edges - so it can be piped straight into
+    /// `dot -Tpng` instead of read off an indented `debug_tree` dump. Mirrors
+    /// `debug_tree`'s cycle guard: a back-edge is rendered as a `(cycle)`-labelled edge
+    /// back to the already-visited node instead of being followed again.
+    pub fn to_dot(&self, index: usize, label: &impl Fn(&T) -> String) -> String {
+        let mut out = String::from("digraph UITreeMap {\n");
+        let mut visited = UIHashSet::new();
+        self.to_dot_node(index, label, &mut out, &mut visited);
+        out.push_str("}\n");
+        out
+    }
+
+    fn to_dot_node(&self, index: usize, label: &impl Fn(&T) -> String, out: &mut String, visited: &mut UIHashSet<usize>) {
+        if !visited.insert(index) {
+            return;
+        }
+
+        let node = &self.nodes[index];
+        out.push_str(&format!("  {} [label=\"{}\"];\n", node.index, escape_dot_label(&label(&node.data))));
+
+        for &child in &node.children {
+            if visited.contains(&child) {
+                out.push_str(&format!("  {} -> {} [label=\"(cycle)\"];\n", node.index, child));
+                continue;
+            }
+            out.push_str(&format!("  {} -> {};\n", node.index, child));
+            self.to_dot_node(child, label, out, visited);
+        }
+    }
+
+    /// Score every node's `name` against `query` with an fzf-style subsequence matcher
+    /// and return the matching node indices sorted by descending score. `query` must
+    /// appear as an ordered (case-insensitive) subsequence of a name for that node to
+    /// match at all; see [`fuzzy_score`] for how the score itself is computed.
+    pub fn fuzzy_find(&self, query: &str) -> Vec<(usize, i32)> {
+        let mut matches: Vec<(usize, i32)> = self
+            .nodes
+            .iter()
+            .filter_map(|node| fuzzy_score(&node.name, query).map(|(score, _)| (node.index, score)))
+            .collect();
+
+        matches.sort_by(|a, b| b.1.cmp(&a.1));
+        matches
+    }
+
+    /// Same ranking as [`Self::fuzzy_find`], but also returns the matched character
+    /// offsets in each name so a caller can highlight which characters satisfied the
+    /// query - the way editor tree explorers bold the matched letters.
+    pub fn fuzzy_find_with_positions(&self, query: &str) -> Vec<(usize, i32, Vec<usize>)> {
+        let mut matches: Vec<(usize, i32, Vec<usize>)> = self
+            .nodes
+            .iter()
+            .filter_map(|node| fuzzy_score(&node.name, query).map(|(score, positions)| (node.index, score, positions)))
+            .collect();
+
+        matches.sort_by(|a, b| b.1.cmp(&a.1));
+        matches
+    }
+
     pub fn get_path_to_element(&self, index: usize) -> Vec<usize> {
         let mut path = Vec::new();
         let mut current_index = index;
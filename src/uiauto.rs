@@ -4,6 +4,11 @@ use crate::xpath::get_path_to_element;
 use crate::xpath::XpathElement;
 use crate::logging::PerformanceTimer;
 use crate::{log_uiauto_operation};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+use uiautomation::types::UIProperty;
 use uiautomation::{controls::ControlType, UIAutomation, UIElement};
 
 trait ConvertToControlType {
@@ -69,6 +74,113 @@ enum FindResult {
     FoundSingle(UIElement),
     FoundMultiple(Vec<UIElement>),
     NotFound,
+    /// The search was cut short by a [`SearchLifetime`] - either its cancel flag was
+    /// flipped from another thread, or its deadline passed.
+    Aborted,
+}
+
+/// How a candidate's actual name is compared against the name requested in the xpath.
+///
+/// `Exact` is the historical behavior (passed straight into the UIA matcher's `.name()`).
+/// `Prefix` and `Flex` are fallbacks tried only after an exact match comes up empty, to
+/// tolerate cosmetic drift such as trailing whitespace, localized suffixes, or accelerator
+/// markers (`&File`) that the application renders but the xpath doesn't capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchStrategy {
+    /// Exact, case-sensitive string match - the only strategy used before this existed.
+    Exact,
+    /// Candidate name starts with the query, case-insensitive.
+    Prefix,
+    /// Every character of the query appears, in order, somewhere in the candidate name
+    /// (case-insensitive), scored by gap length between matches with a bonus for runs of
+    /// consecutive matches.
+    Flex,
+}
+
+/// A candidate clearing this score (see [`flex_match_score`]) is resolved straight to
+/// `FoundSingle` instead of falling through to `FoundMultiple`.
+const NAME_MATCH_THRESHOLD: i32 = -4;
+
+/// Score `candidate` against `query` under `strategy`, or `None` if it doesn't match at
+/// all. Higher scores are better matches; scores are only comparable within the same
+/// strategy.
+fn score_candidate_name(candidate: &str, query: &str, strategy: MatchStrategy) -> Option<i32> {
+    match strategy {
+        MatchStrategy::Exact => (candidate == query).then_some(0),
+        MatchStrategy::Prefix => {
+            let matches = candidate.to_lowercase().starts_with(&query.to_lowercase());
+            matches.then(|| query.chars().count() as i32 - candidate.chars().count() as i32)
+        }
+        MatchStrategy::Flex => flex_match_score(query, candidate),
+    }
+}
+
+/// Ordered-subsequence fuzzy match: every character of `query` must appear, in order,
+/// case-insensitively, somewhere in `candidate`. Returns `None` if it doesn't match;
+/// otherwise a score built from the gaps between matched characters (a consecutive match
+/// adds a bonus, a gap subtracts its length), so tighter, more contiguous matches score
+/// higher.
+fn flex_match_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut score = 0i32;
+    let mut last_match: Option<usize> = None;
+    let mut query_index = 0;
+
+    for (candidate_index, &ch) in candidate.iter().enumerate() {
+        if query_index >= query.len() {
+            break;
+        }
+        if ch == query[query_index] {
+            if let Some(last) = last_match {
+                let gap = candidate_index - last - 1;
+                if gap == 0 {
+                    score += 2;
+                } else {
+                    score -= gap as i32;
+                }
+            }
+            last_match = Some(candidate_index);
+            query_index += 1;
+        }
+    }
+
+    (query_index == query.len()).then_some(score)
+}
+
+/// Score every candidate's name against `query` under `strategy` and resolve the result:
+/// the best-scoring candidate wins outright as `FoundSingle` once it clears
+/// [`NAME_MATCH_THRESHOLD`], otherwise every candidate that matched at all (however
+/// weakly) is handed back as `FoundMultiple` for the caller's existing disambiguation
+/// logic (lucky punch / xpath validation) to sort out.
+fn resolve_by_name_strategy(candidates: Vec<UIElement>, query: &str, strategy: MatchStrategy) -> FindResult {
+    let mut scored: Vec<(i32, UIElement)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let name = candidate.get_name().unwrap_or_default();
+            score_candidate_name(&name, query, strategy).map(|score| (score, candidate))
+        })
+        .collect();
+
+    if scored.is_empty() {
+        return FindResult::NotFound;
+    }
+
+    scored.sort_by_key(|(score, _)| -score);
+
+    let (best_score, best_element) = scored[0].clone();
+    if best_score >= NAME_MATCH_THRESHOLD {
+        log::debug!("{:?} match resolved '{}' with score {}", strategy, query, best_score);
+        return FindResult::FoundSingle(best_element);
+    }
+
+    log::debug!("{:?} match for '{}' found {} candidates, none cleared the threshold", strategy, query, scored.len());
+    FindResult::FoundMultiple(scored.into_iter().map(|(_, element)| element).collect())
 }
 
 impl std::fmt::Debug for FindResult {
@@ -82,11 +194,128 @@ impl std::fmt::Debug for FindResult {
                 write!(f, "FoundMultiple({} elements)", elements.len())
             },
             FindResult::NotFound => write!(f, "NotFound"),
+            FindResult::Aborted => write!(f, "Aborted"),
+        }
+    }
+}
+
+/// A shared cancel flag plus an optional deadline threaded through a traversal, mirroring
+/// broot's `TaskLifetime`: a caller on another thread flips `cancel` to abort a search
+/// stuck at the "lucky punch" `search_depth = 99`, or the search trips its own `deadline`
+/// once `Instant::now()` passes it. Checked before every `matcher.find_all()` call and
+/// between traversal steps, so a stuck search returns cleanly instead of hanging.
+#[derive(Clone)]
+struct SearchLifetime {
+    cancel: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl SearchLifetime {
+    fn new(cancel: Arc<AtomicBool>, deadline: Option<Duration>) -> Self {
+        SearchLifetime { cancel, deadline: deadline.map(|d| Instant::now() + d) }
+    }
+
+    /// No cancel flag and no deadline of its own, for callers that don't need to interrupt
+    /// a search; each `find_all` is still cut off after [`DEFAULT_SEARCH_TIMEOUT`] by
+    /// [`find_all_bounded`].
+    fn unbounded() -> Self {
+        SearchLifetime { cancel: Arc::new(AtomicBool::new(false)), deadline: None }
+    }
+
+    fn is_expired(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst) || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// A candidate's regenerated xpath clearing this similarity score is accepted as the
+/// resolved element in the `xpath_validation` fallback, instead of requiring a byte-for-
+/// byte equality match.
+const XPATH_SIMILARITY_THRESHOLD: f32 = 0.6;
+
+/// Split an xpath into its `/`-delimited segments, dropping the empty leading segment
+/// (every xpath here starts with `/`).
+fn xpath_segments(xpath: &str) -> Vec<&str> {
+    xpath.split('/').filter(|segment| !segment.is_empty()).collect()
+}
+
+/// The value of a `[@attr=\"value\"]` predicate on a single xpath segment, if present.
+fn segment_attr<'a>(segment: &'a str, attr: &str) -> Option<&'a str> {
+    let needle = format!("[@{}=\\\"", attr);
+    let start = segment.find(&needle)? + needle.len();
+    let end = segment[start..].find("\\\"")?;
+    Some(&segment[start..start + end])
+}
+
+/// Cost of substituting `a` for `b` when comparing two xpath segments for similarity:
+/// zero if they're identical, a small cost if they still agree on a stable identifying
+/// attribute (`AutomationId` or `ClassName`, which don't drift the way a localized `Name`
+/// does), full cost otherwise.
+fn segment_substitution_cost(a: &str, b: &str) -> f32 {
+    if a == b {
+        return 0.0;
+    }
+    for attr in ["AutomationId", "ClassName"] {
+        if let (Some(value_a), Some(value_b)) = (segment_attr(a, attr), segment_attr(b, attr)) {
+            if value_a == value_b {
+                return 0.3;
+            }
         }
     }
+    1.0
+}
+
+/// Normalized similarity between two xpaths in `[0.0, 1.0]`: a Levenshtein edit distance
+/// over their `/`-delimited segment sequences - weighting a substitution that still agrees
+/// on a stable attribute lower than a plain rename - divided by the longer segment count.
+fn xpath_similarity(a: &str, b: &str) -> f32 {
+    let segments_a = xpath_segments(a);
+    let segments_b = xpath_segments(b);
+
+    let len_a = segments_a.len();
+    let len_b = segments_b.len();
+    if len_a == 0 && len_b == 0 {
+        return 1.0;
+    }
+
+    let mut row: Vec<f32> = (0..=len_b).map(|j| j as f32).collect();
+    for i in 1..=len_a {
+        let mut prev_diag = row[0];
+        row[0] = i as f32;
+        for j in 1..=len_b {
+            let tmp = row[j];
+            let substitution = prev_diag + segment_substitution_cost(segments_a[i - 1], segments_b[j - 1]);
+            let deletion = row[j] + 1.0;
+            let insertion = row[j - 1] + 1.0;
+            row[j] = substitution.min(deletion).min(insertion);
+            prev_diag = tmp;
+        }
+    }
+
+    1.0 - (row[len_b] / len_a.max(len_b) as f32)
 }
 
 pub fn get_element_by_xpath(xpath: String) -> Option<Element> {
+    get_element_by_xpath_impl(xpath, MatchStrategy::Exact, &SearchLifetime::unbounded())
+}
+
+/// As [`get_element_by_xpath`], but falls back to `strategy` (see [`MatchStrategy`]) for
+/// any element along the path whose name doesn't match exactly - lets a caller opt into
+/// prefix or flex name matching when the target application is known to render slightly
+/// different captions than its xpath was captured with.
+pub fn get_element_by_xpath_with_strategy(xpath: String, strategy: MatchStrategy) -> Option<Element> {
+    get_element_by_xpath_impl(xpath, strategy, &SearchLifetime::unbounded())
+}
+
+/// As [`get_element_by_xpath`], but interruptible: `cancel` can be flipped from another
+/// thread to abort a search stuck at the "lucky punch" `search_depth = 99`, and `deadline`
+/// bounds how long the search is allowed to run. Returns `None` on abort or timeout, the
+/// same as any other not-found outcome, so a caller such as the egui front end can unstick
+/// itself instead of hanging.
+pub fn get_element_by_xpath_with_cancel(xpath: String, cancel: Arc<AtomicBool>, deadline: Option<Duration>) -> Option<Element> {
+    get_element_by_xpath_impl(xpath, MatchStrategy::Exact, &SearchLifetime::new(cancel, deadline))
+}
+
+fn get_element_by_xpath_impl(xpath: String, strategy: MatchStrategy, lifetime: &SearchLifetime) -> Option<Element> {
     let _timer = PerformanceTimer::new("get_element_by_xpath");
     log_uiauto_operation!(log::Level::Info, "FIND_BY_XPATH", 
                          &format!("xpath_length={}", xpath.len()), 
@@ -128,13 +357,20 @@ pub fn get_element_by_xpath(xpath: String) -> Option<Element> {
     log::debug!("Starting element traversal from root element");
     
     'outer: for (element_index, element) in path_to_element.iter().enumerate() {
-        log_uiauto_operation!(log::Level::Debug, "TRAVERSE", 
-                             &format!("element={}/{}", element_index + 1, path_to_element.len()), 
-                             "Searching for element: control_type={}, attributes={}", 
+        if lifetime.is_expired() {
+            log_uiauto_operation!(log::Level::Warn, "TRAVERSE", "aborted",
+                                 "Search cancelled or deadline exceeded before element {}/{}",
+                                 element_index + 1, path_to_element.len());
+            return None;
+        }
+
+        log_uiauto_operation!(log::Level::Debug, "TRAVERSE",
+                             &format!("element={}/{}", element_index + 1, path_to_element.len()),
+                             "Searching for element: control_type={}, attributes={}",
                              element.control_type, element.attribute_count);
-        
-        let found = get_next_element(root.clone(), &element.clone(), search_depth);
-        
+
+        let found = get_next_element(root.clone(), &element.clone(), search_depth, strategy, lifetime);
+
         log::debug!("Search result for element {}: {:?}", element_index + 1, found);
         
         match found {
@@ -156,7 +392,7 @@ pub fn get_element_by_xpath(xpath: String) -> Option<Element> {
                 let final_element = path_to_element.last().unwrap();
                 
                 log::debug!("Attempting lucky punch with final element: {:?}", final_element);
-                let found = get_next_element(root.clone(), &final_element.clone(), search_depth);
+                let found = get_next_element(root.clone(), &final_element.clone(), search_depth, strategy, lifetime);
                 
                 match found {
                     FindResult::FoundSingle(found_element) => {
@@ -167,56 +403,79 @@ pub fn get_element_by_xpath(xpath: String) -> Option<Element> {
                         break; // Exit the loop after finding the target element
                     },
                     FindResult::FoundMultiple(found_elements) => {
-                        log_uiauto_operation!(log::Level::Warn, "TRAVERSE", "xpath_validation", 
-                                             "Found {} candidates - validating by xpath generation", 
+                        log_uiauto_operation!(log::Level::Warn, "TRAVERSE", "xpath_validation",
+                                             "Found {} candidates - ranking by xpath similarity",
                                              found_elements.len());
-                        
-                        // loop through the found elements and construct a new xpath for each element
-                        // and check if the xpath matches the target element
+
+                        // loop through the found elements, construct a new xpath for each one,
+                        // and rank them by similarity to the target xpath rather than requiring
+                        // a byte-for-byte match
+                        let mut scored: Vec<(f32, UIElement)> = Vec::new();
                         for (candidate_index, found_element) in found_elements.iter().enumerate() {
                             log::debug!("Validating candidate {} of {}", candidate_index + 1, found_elements.len());
-                            
+
                             if let Ok(optional_point) = found_element.get_clickable_point() {
                                 let point = optional_point.unwrap_or_default();
-                                log::debug!("Candidate {} clickable point: ({}, {})", 
+                                log::debug!("Candidate {} clickable point: ({}, {})",
                                            candidate_index + 1, point.get_x(), point.get_y());
-                                
+
                                 let xpath_candidate = generate_xpath(point.get_x(), point.get_y());
-                                
-                                if xpath_candidate == xpath {
-                                    log_uiauto_operation!(log::Level::Info, "TRAVERSE", "xpath_match", 
-                                                         "Found matching element by xpath validation: {}", 
-                                                         found_element.get_name().unwrap_or_default());
-                                    root = found_element.clone();
-                                    break 'outer; // Exit the inner and outer loop after finding the target element
-                                } else {
-                                    log::debug!("Candidate {} xpath mismatch. Expected: {}, Got: {}", 
-                                               candidate_index + 1, 
-                                               if xpath.len() > 100 { &xpath[..100] } else { &xpath },
-                                               if xpath_candidate.len() > 100 { &xpath_candidate[..100] } else { &xpath_candidate });
-                                }
+                                let similarity = xpath_similarity(&xpath_candidate, &xpath);
+                                log::debug!("Candidate {} xpath similarity: {:.3}. Expected: {}, Got: {}",
+                                           candidate_index + 1, similarity,
+                                           if xpath.len() > 100 { &xpath[..100] } else { &xpath },
+                                           if xpath_candidate.len() > 100 { &xpath_candidate[..100] } else { &xpath_candidate });
+                                scored.push((similarity, found_element.clone()));
                             } else {
                                 log::debug!("Failed to get clickable point for candidate {}", candidate_index + 1);
                             }
                         }
-                        
-                        log_uiauto_operation!(log::Level::Error, "TRAVERSE", "no_match", 
-                                             "No matching element found after xpath validation");
+
+                        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+                        if let Some((best_score, best_element)) = scored.first() {
+                            if *best_score >= XPATH_SIMILARITY_THRESHOLD {
+                                if let Some((runner_up_score, runner_up)) = scored.get(1) {
+                                    log_uiauto_operation!(log::Level::Info, "TRAVERSE", "xpath_similarity_runner_up",
+                                                         "Runner-up candidate scored {:.3}: {}",
+                                                         runner_up_score, runner_up.get_name().unwrap_or_default());
+                                }
+                                log_uiauto_operation!(log::Level::Info, "TRAVERSE", "xpath_match",
+                                                     "Found best matching element by xpath similarity ({:.3}): {}",
+                                                     best_score, best_element.get_name().unwrap_or_default());
+                                root = best_element.clone();
+                                break 'outer; // Exit the inner and outer loop after finding the target element
+                            }
+                        }
+
+                        log_uiauto_operation!(log::Level::Error, "TRAVERSE", "no_match",
+                                             "No candidate cleared the xpath similarity threshold");
                         return None; // Return None if we find multiple elements again
-                        
+
                     },
                     FindResult::NotFound => {
-                        log_uiauto_operation!(log::Level::Error, "TRAVERSE", "final_not_found", 
+                        log_uiauto_operation!(log::Level::Error, "TRAVERSE", "final_not_found",
                                              "Final element not found during lucky punch attempt");
                         return None;
+                    },
+                    FindResult::Aborted => {
+                        log_uiauto_operation!(log::Level::Warn, "TRAVERSE", "aborted",
+                                             "Lucky punch attempt cancelled or deadline exceeded");
+                        return None;
                     }
-                } 
+                }
             },
             FindResult::NotFound => {
-                log_uiauto_operation!(log::Level::Error, "TRAVERSE", 
-                                     &format!("element={}/{}", element_index + 1, path_to_element.len()), 
+                log_uiauto_operation!(log::Level::Error, "TRAVERSE",
+                                     &format!("element={}/{}", element_index + 1, path_to_element.len()),
                                      "Element not found: control_type={}", element.control_type);
                 return None;
+            },
+            FindResult::Aborted => {
+                log_uiauto_operation!(log::Level::Warn, "TRAVERSE", "aborted",
+                                     "Search cancelled or deadline exceeded while searching for element {}/{}",
+                                     element_index + 1, path_to_element.len());
+                return None;
             }
         }
     }
@@ -243,73 +502,216 @@ pub fn get_element_by_xpath(xpath: String) -> Option<Element> {
     Some(element)
 }
 
-fn get_next_element(root: UIElement, element: &XpathElement<'_>, depth: u32 ) -> FindResult {
-    let _timer = PerformanceTimer::new("get_next_element");
-    log_uiauto_operation!(log::Level::Debug, "SEARCH", 
-                         &format!("control_type={}, depth={}", element.control_type, depth), 
-                         "Searching for element with {} attributes", element.attribute_count);
+/// Owned copy of the handful of `XpathElement` fields `build_element_matcher` reads, so
+/// a search can be handed to [`find_all_bounded`]'s worker thread without `XpathElement`'s
+/// borrowed lifetime following it across the `'static` bound `thread::spawn` requires.
+#[derive(Clone)]
+struct OwnedSearchElement {
+    control_type: String,
+    classname: Option<String>,
+    name: Option<String>,
+    attributes: Vec<OwnedAttribute>,
+}
 
-    let uia = UIAutomation::new().unwrap();
-    let matcher = uia.create_matcher().from(root.clone()).depth(depth);
+#[derive(Clone)]
+struct OwnedAttribute {
+    key: String,
+    value: String,
+    op: crate::xpath::PredicateOp,
+}
 
-    let control_type = ControlType::from_str(element.control_type);
-    let matcher = matcher.control_type(control_type);
-    log::debug!("Added control type filter: {:?}", control_type);
+impl From<&XpathElement<'_>> for OwnedSearchElement {
+    fn from(element: &XpathElement<'_>) -> Self {
+        OwnedSearchElement {
+            control_type: element.control_type.to_string(),
+            classname: element.classname.map(str::to_string),
+            name: element.name.map(str::to_string),
+            attributes: element
+                .attributes
+                .iter()
+                .map(|attr| OwnedAttribute { key: attr.key.to_string(), value: attr.value.to_string(), op: attr.op })
+                .collect(),
+        }
+    }
+}
+
+fn build_element_matcher<'a>(uia: &'a UIAutomation, root: UIElement, element: &OwnedSearchElement, depth: u32, with_name: bool) -> uiautomation::UIMatcher<'a> {
+    let mut matcher = uia.create_matcher().from(root).depth(depth);
 
-    let matcher = if element.name.is_some() {
-        log::debug!("Adding name filter: {}", element.name.unwrap());
-        matcher.name(element.name.unwrap())
+    // `*` matches any control type, so skip the `.control_type()` filter entirely rather
+    // than mapping it to some concrete `ControlType`.
+    if element.control_type == "*" {
+        log::trace!("Wildcard control type - no control type filter applied");
     } else {
-        log::trace!("No name filter specified");
-        matcher
-    };
-    
-    let matcher = if element.classname.is_some() {
-        log::debug!("Adding classname filter: {}", element.classname.unwrap());
-        matcher.classname(element.classname.unwrap())
+        let control_type = ControlType::from_str(&element.control_type);
+        matcher = matcher.control_type(control_type);
+        log::debug!("Added control type filter: {:?}", control_type);
+    }
+
+    // `.name()`/`.classname()` compare exactly, so they only take an `Eq` predicate; a
+    // `contains()`/`startswith()` one on Name or ClassName becomes a PropertyFilter below.
+    let is_eq = |key: &str| element.attributes.iter().filter(|attr| attr.key == key).all(|attr| attr.op == crate::xpath::PredicateOp::Eq);
+
+    if with_name {
+        if let Some(name) = element.name.as_deref().filter(|_| is_eq("Name")) {
+            log::debug!("Adding name filter: {}", name);
+            matcher = matcher.name(name);
+        } else {
+            log::trace!("No name filter specified");
+        }
+    }
+
+    if let Some(classname) = element.classname.as_deref().filter(|_| is_eq("ClassName")) {
+        log::debug!("Adding classname filter: {}", classname);
+        matcher = matcher.classname(classname);
     } else {
         log::trace!("No classname filter specified");
-        matcher
-    };
+    }
 
-    // TODO: add a filter function for automationid
-    // let matcher = if element.automationid.is_some() {matcher.automationid(element.automationid)} else {matcher};
-    
-    if element.automationid.is_some() {
-        log::debug!("AutomationId filter requested but not implemented: {}", element.automationid.unwrap());
+    // The keys `attribute_to_property` maps (AutomationId, HelpText, AccessKey, FrameworkId,
+    // LocalizedControlType) and non-exact Name/ClassName predicates are attached as
+    // PropertyFilters, combined with AND via AllFilters since the matcher builder only
+    // accepts a single `.filter(...)` call. A predicate on any other key gets no filter.
+    let property_filters: Vec<Box<dyn uiautomation::filters::MatcherFilter>> = element
+        .attributes
+        .iter()
+        .filter_map(|attr| {
+            let property = match attr.key.as_str() {
+                "Name" if attr.op != crate::xpath::PredicateOp::Eq && with_name => UIProperty::Name,
+                "ClassName" if attr.op != crate::xpath::PredicateOp::Eq => UIProperty::ClassName,
+                key => attribute_to_property(key)?,
+            };
+            log::debug!("Adding property filter: {}={} ({:?})", attr.key, attr.value, attr.op);
+            Some(Box::new(PropertyFilter { property, expected: attr.value.clone(), op: attr.op.into() }) as Box<dyn uiautomation::filters::MatcherFilter>)
+        })
+        .collect();
+
+    if !property_filters.is_empty() {
+        matcher = matcher.filter(Box::new(AllFilters(property_filters)));
+    } else {
+        log::trace!("No extra property filters specified");
     }
 
+    matcher
+}
+
+/// How long to give a single bounded search before giving up, when `lifetime` has no
+/// deadline of its own - long enough for a legitimate deep scan, short enough that the
+/// depth-99 "lucky punch" search this module exists to bound can't hang indefinitely.
+const DEFAULT_SEARCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Run `find_all` on a dedicated worker thread and wait up to `lifetime`'s remaining
+/// deadline (or [`DEFAULT_SEARCH_TIMEOUT`] if it has none) for it to finish, so the
+/// search itself - not just the gaps between calls - can't hang forever. `element` is
+/// cloned into the worker rather than borrowed, and the worker creates its own
+/// `UIAutomation` instance, since both `XpathElement` and `UIMatcher` carry borrowed
+/// lifetimes that can't cross `thread::spawn`'s `'static` bound. Returns `None` on
+/// timeout; a failure to create the worker's `UIAutomation` instance comes back as
+/// `Some(Err(..))`.
+fn find_all_bounded(root: UIElement, element: &OwnedSearchElement, depth: u32, with_name: bool, lifetime: &SearchLifetime) -> Option<uiautomation::Result<Vec<UIElement>>> {
+    let element = element.clone();
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let result = UIAutomation::new().and_then(|uia| build_element_matcher(&uia, root, &element, depth, with_name).find_all());
+        let _ = tx.send(result);
+    });
+
+    let timeout = lifetime
+        .deadline
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+        .unwrap_or(DEFAULT_SEARCH_TIMEOUT);
+    rx.recv_timeout(timeout).ok()
+}
+
+fn get_next_element(root: UIElement, element: &XpathElement<'_>, depth: u32, strategy: MatchStrategy, lifetime: &SearchLifetime) -> FindResult {
+    let _timer = PerformanceTimer::new("get_next_element");
+    log_uiauto_operation!(log::Level::Debug, "SEARCH",
+                         &format!("control_type={}, depth={}", element.control_type, depth),
+                         "Searching for element with {} attributes", element.attribute_count);
+
+    if lifetime.is_expired() {
+        log::debug!("Search lifetime expired before element search");
+        return FindResult::Aborted;
+    }
+
+    let owned_element = OwnedSearchElement::from(element);
+
     log::debug!("Executing element search with configured filters");
-    
-    if let Ok(found_elements) = matcher.find_all() { 
-        log::debug!("Search completed. Found {} elements", found_elements.len());
-        
-        if found_elements.len() == 1 {
-            let element_name = found_elements[0].get_name().unwrap_or_default();
-            log_uiauto_operation!(log::Level::Debug, "SEARCH", "single_match", 
-                                 "Found exactly one element: {}", element_name);
-            return FindResult::FoundSingle(found_elements[0].clone());
-        } else if found_elements.len() > 1 {
-            log_uiauto_operation!(log::Level::Debug, "SEARCH", "multiple_matches", 
-                                 "Found {} elements:", found_elements.len());
-            
-            for (i, elem) in found_elements.iter().enumerate() {
-                let elem_name = elem.get_name().unwrap_or_default();
-                let elem_classname = elem.get_classname().unwrap_or_default();
-                log::debug!("  Element {}: name='{}', classname='{}'", i + 1, elem_name, elem_classname);
-            }
-            
-            return FindResult::FoundMultiple(found_elements);
-        } else {
-            log_uiauto_operation!(log::Level::Debug, "SEARCH", "no_matches", 
-                                 "No elements found matching the criteria");
+
+    let found_elements = match find_all_bounded(root.clone(), &owned_element, depth, true, lifetime) {
+        Some(Ok(found)) => found,
+        Some(Err(_)) => {
+            log_uiauto_operation!(log::Level::Error, "SEARCH", "search_error",
+                                 "Error occurred during element search");
             return FindResult::NotFound;
         }
-    } else {
-        log_uiauto_operation!(log::Level::Error, "SEARCH", "search_error", 
-                             "Error occurred during element search");
+        None => {
+            log::debug!("Search timed out while executing element search");
+            return FindResult::Aborted;
+        }
+    };
+
+    log::debug!("Search completed. Found {} elements", found_elements.len());
+
+    if found_elements.len() == 1 {
+        let element_name = found_elements[0].get_name().unwrap_or_default();
+        log_uiauto_operation!(log::Level::Debug, "SEARCH", "single_match",
+                             "Found exactly one element: {}", element_name);
+        return FindResult::FoundSingle(found_elements[0].clone());
+    }
+
+    if found_elements.is_empty() {
+        if let (Some(name), false) = (element.name, strategy == MatchStrategy::Exact) {
+            if lifetime.is_expired() {
+                log::debug!("Search lifetime expired before name-fallback search");
+                return FindResult::Aborted;
+            }
+            log_uiauto_operation!(log::Level::Debug, "SEARCH", "name_fallback",
+                                 "Exact name match failed for '{}', retrying with {:?} matching", name, strategy);
+            let candidates = match find_all_bounded(root, &owned_element, depth, false, lifetime) {
+                Some(Ok(candidates)) => candidates,
+                Some(Err(_)) => {
+                    log_uiauto_operation!(log::Level::Error, "SEARCH", "search_error",
+                                         "Error occurred during fallback element search");
+                    return FindResult::NotFound;
+                }
+                None => {
+                    log::debug!("Search timed out while executing name-fallback element search");
+                    return FindResult::Aborted;
+                }
+            };
+            return resolve_by_name_strategy(candidates, name, strategy);
+        }
+
+        log_uiauto_operation!(log::Level::Debug, "SEARCH", "no_matches",
+                             "No elements found matching the criteria");
         return FindResult::NotFound;
     }
+
+    log_uiauto_operation!(log::Level::Debug, "SEARCH", "multiple_matches",
+                         "Found {} elements:", found_elements.len());
+
+    for (i, elem) in found_elements.iter().enumerate() {
+        let elem_name = elem.get_name().unwrap_or_default();
+        let elem_classname = elem.get_classname().unwrap_or_default();
+        log::debug!("  Element {}: name='{}', classname='{}'", i + 1, elem_name, elem_classname);
+    }
+
+    // A `[n]` positional index in the xpath disambiguates otherwise-identical siblings
+    // deterministically, instead of falling through to the lucky-punch/xpath-validation
+    // fallback in `get_element_by_xpath_impl`.
+    if let Some(index) = element.index {
+        if let Some(found_element) = found_elements.get(index.saturating_sub(1)) {
+            log_uiauto_operation!(log::Level::Debug, "SEARCH", "positional_index",
+                                 "Resolved to element {} of {} via positional index", index, found_elements.len());
+            return FindResult::FoundSingle(found_element.clone());
+        }
+        log_uiauto_operation!(log::Level::Warn, "SEARCH", "positional_index_out_of_range",
+                             "Positional index {} out of range for {} matches", index, found_elements.len());
+    }
+
+    FindResult::FoundMultiple(found_elements)
 }
 
 pub fn get_ui_element_by_xpath(xpath: String) -> Option<UIElement> {
@@ -332,6 +734,84 @@ pub fn get_ui_element_by_xpath(xpath: String) -> Option<UIElement> {
     get_ui_element_by_runtimeid(runtime_id)
 }
 
+/// How a [`PropertyFilter`] compares an element's actual property value against the one
+/// requested in the xpath. `Contains`/`StartsWith` are produced by the `contains()`/
+/// `startswith()` xpath predicate syntax; everything else still produces `Equals`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchOp {
+    Equals,
+    Contains,
+    StartsWith,
+}
+
+impl From<crate::xpath::PredicateOp> for MatchOp {
+    fn from(op: crate::xpath::PredicateOp) -> Self {
+        match op {
+            crate::xpath::PredicateOp::Eq => MatchOp::Equals,
+            crate::xpath::PredicateOp::Contains => MatchOp::Contains,
+            crate::xpath::PredicateOp::StartsWith => MatchOp::StartsWith,
+        }
+    }
+}
+
+/// A generic `[@Property='value']` xpath predicate, implemented as a UIA `MatcherFilter`
+/// alongside the existing [`RuntimeIdFilter`] - covers the keys [`attribute_to_property`]
+/// maps (`AutomationId`, `HelpText`, `AccessKey`, `FrameworkId`, `LocalizedControlType`)
+/// and non-exact `Name`/`ClassName` predicates; a predicate on any other key gets no filter.
+struct PropertyFilter {
+    property: UIProperty,
+    expected: String,
+    op: MatchOp,
+}
+
+impl uiautomation::filters::MatcherFilter for PropertyFilter {
+    fn judge(&self, element: &UIElement) -> uiautomation::Result<bool> {
+        let actual = element.get_property_value(self.property)?.to_string();
+        let matches = match self.op {
+            MatchOp::Equals => actual == self.expected,
+            MatchOp::Contains => actual.contains(&self.expected),
+            MatchOp::StartsWith => actual.starts_with(&self.expected),
+        };
+        if matches {
+            log::trace!("Property {:?} match found: {}", self.property, actual);
+        }
+        Ok(matches)
+    }
+}
+
+/// Combines multiple `MatcherFilter`s with logical AND, so `get_next_element` can attach
+/// one [`PropertyFilter`] per extra xpath predicate even though the UIA matcher builder
+/// only accepts a single `.filter(...)` call.
+struct AllFilters(Vec<Box<dyn uiautomation::filters::MatcherFilter>>);
+
+impl uiautomation::filters::MatcherFilter for AllFilters {
+    fn judge(&self, element: &UIElement) -> uiautomation::Result<bool> {
+        for filter in &self.0 {
+            if !filter.judge(element)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Map an xpath `@Key` to the UIA property it corresponds to, for the keys listed below;
+/// `None` for any other key, including `ClassName`/`Name` (exact ones go through the
+/// matcher's `.classname()`/`.name()`, the rest are mapped in `build_element_matcher`).
+fn attribute_to_property(key: &str) -> Option<UIProperty> {
+    match key {
+        "AutomationId" => Some(UIProperty::AutomationId),
+        "HelpText" => Some(UIProperty::HelpText),
+        "AccessKey" => Some(UIProperty::AccessKey),
+        "FrameworkId" => Some(UIProperty::FrameworkId),
+        "LocalizedControlType" => Some(UIProperty::LocalizedControlType),
+        // RuntimeId isn't exposed as a gettable UIProperty by the `uiautomation` crate -
+        // `RuntimeIdFilter` is the only way to match on it, and it's only ever used for an
+        // exact lookup, never as an xpath predicate.
+        _ => None,
+    }
+}
+
 struct RuntimeIdFilter(Vec<i32>);
 
 impl uiautomation::filters::MatcherFilter for RuntimeIdFilter {
@@ -1,6 +1,8 @@
 use std::thread;
 use std::sync::Mutex;
+use std::sync::atomic::Ordering;
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
 
 use pyo3::prelude::*;
 
@@ -13,10 +15,25 @@ use crate::app_control::launch_or_activate_application;
 use crate::commons::execute_with_timeout;
 
 
-use windows::Win32::Foundation::{RECT, POINT}; //HWND, 
-use windows::Win32::UI::WindowsAndMessaging::{GetCursorPos}; //WindowFromPoint
+use windows::Win32::Foundation::{RECT, POINT, HWND};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetCursorPos, GetDoubleClickTime, GetSystemMetrics, GetWindowRect, SetCursorPos, mouse_event,
+    MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP,
+    MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEEVENTF_WHEEL, MOUSEEVENTF_HWHEEL,
+    SM_CXDOUBLECLK, SM_CYDOUBLECLK, SM_CXSIZEFRAME, SM_CYSIZEFRAME, SM_CYCAPTION,
+}; //WindowFromPoint
+use windows::Win32::Graphics::Gdi::{MonitorFromPoint, MONITOR_FROM_FLAGS};
+use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
+use windows::Win32::UI::HiDpi::{GetDpiForMonitor, MONITOR_DPI_TYPE};
 
-use uiautomation::{UIElement}; //UIAutomation, 
+use uiautomation::{UIElement}; //UIAutomation,
+
+/// The Win32 `WHEEL_DELTA` constant: one wheel "notch" in `mouse_event`'s wheel data.
+const WHEEL_DELTA: i32 = 120;
+
+/// How many intermediate moves a synthesized drag makes between its start and end point.
+const DRAG_STEPS: i32 = 20;
+const DRAG_STEP_DELAY_MS: u64 = 10;
 
 static WINDRIVER: Mutex<Option<WinDriver>> = Mutex::new(None);
 
@@ -29,6 +46,10 @@ pub struct Element {
     handle: isize,
     runtime_id: Vec<i32>,
     bounding_rectangle: RECT,
+    /// The DPI scale (dpi/96) of the monitor `bounding_rectangle` was captured on -
+    /// defaults to `1.0` for elements built directly via `Element::new`, and is set by
+    /// `WinDriver::get_ui_element` to the scale of the monitor under the hit-tested point.
+    scale: f32,
 
 }
 
@@ -44,7 +65,7 @@ impl Element {
             right: bounding_rectangle.2,
             bottom: bounding_rectangle.3,
         };
-        Element { name, xpath, handle, runtime_id , bounding_rectangle}
+        Element { name, xpath, handle, runtime_id , bounding_rectangle, scale: 1.0 }
     }
 
     pub fn __repr__(&self) -> PyResult<String> {
@@ -70,7 +91,30 @@ impl Element {
     pub fn get_runtime_id(&self) -> Vec<i32> {
         self.runtime_id.clone()
     }
-    
+
+    /// The DPI scale of the monitor this element's `bounding_rectangle` was captured on.
+    pub fn get_capture_scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// This element's bounding rectangle in physical pixels, as `(left, top, right, bottom)`.
+    pub fn get_bounding_rectangle(&self) -> (i32, i32, i32, i32) {
+        (self.bounding_rectangle.left, self.bounding_rectangle.top, self.bounding_rectangle.right, self.bounding_rectangle.bottom)
+    }
+
+    /// This element's bounding rectangle converted to logical (DPI-scaled) coordinates,
+    /// using `scale`, so a caller working in logical space doesn't have to divide by the
+    /// capturing monitor's scale itself.
+    pub fn get_bounding_rectangle_logical(&self) -> (i32, i32, i32, i32) {
+        let r = self.bounding_rectangle;
+        (
+            (r.left as f32 / self.scale).round() as i32,
+            (r.top as f32 / self.scale).round() as i32,
+            (r.right as f32 / self.scale).round() as i32,
+            (r.bottom as f32 / self.scale).round() as i32,
+        )
+    }
+
     pub fn send_click(&self) -> PyResult<()> {
         if let Ok(e) = convert_to_ui_element(self) {
             match e.click() {
@@ -81,7 +125,215 @@ impl Element {
                     println!("Error clicking on element: {:?}", e);
                     return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Click failed"));
                 }
-                
+
+            }
+        } else {
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element not found"));
+        }
+        PyResult::Ok(())
+    }
+
+    pub fn send_right_click(&self) -> PyResult<()> {
+        if let Ok(e) = convert_to_ui_element(self) {
+            match e.right_click() {
+                Ok(_) => {
+                    println!("Right clicked on element: {:#?}", e);
+                }
+                Err(e) => {
+                    println!("Error right clicking on element: {:?}", e);
+                    return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Right click failed"));
+                }
+            }
+        } else {
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element not found"));
+        }
+        PyResult::Ok(())
+    }
+
+    /// Click this element's center twice, staying inside the system's double-click box and
+    /// double-click time so Windows coalesces the two clicks into a real double click
+    /// instead of replaying two single clicks.
+    pub fn send_double_click(&self) -> PyResult<()> {
+        let Ok(e) = convert_to_ui_element(self) else {
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element not found"));
+        };
+
+        let (x, y) = element_center(&e)?;
+        let gap_ms = unsafe {
+            // Both clicks land on the exact same point, so SM_CXDOUBLECLK/SM_CYDOUBLECLK
+            // are trivially satisfied; only the double-click time needs honoring.
+            let _box_width = GetSystemMetrics(SM_CXDOUBLECLK);
+            let _box_height = GetSystemMetrics(SM_CYDOUBLECLK);
+            (GetDoubleClickTime() / 4).max(1) as u64
+        };
+
+        if let Err(e) = synthesize_click(x, y, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP) {
+            return Err(e);
+        }
+        thread::sleep(Duration::from_millis(gap_ms));
+        if let Err(e) = synthesize_click(x, y, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP) {
+            return Err(e);
+        }
+
+        println!("Double clicked on element: {}", self.name);
+        PyResult::Ok(())
+    }
+
+    /// Middle-click this element's center; uiautomation's `UIElement` has no middle-click
+    /// of its own, so this is synthesized directly via `mouse_event`.
+    pub fn send_middle_click(&self) -> PyResult<()> {
+        let Ok(e) = convert_to_ui_element(self) else {
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element not found"));
+        };
+
+        let (x, y) = element_center(&e)?;
+        synthesize_click(x, y, MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP)?;
+
+        println!("Middle clicked on element: {}", self.name);
+        PyResult::Ok(())
+    }
+
+    /// Move the cursor to this element's center without clicking.
+    pub fn hover(&self) -> PyResult<()> {
+        let Ok(e) = convert_to_ui_element(self) else {
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element not found"));
+        };
+
+        let (x, y) = element_center(&e)?;
+        unsafe {
+            if let Err(e) = SetCursorPos(x, y) {
+                println!("Error moving cursor to element: {:?}", e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move cursor"));
+            }
+        }
+
+        println!("Hovering over element: {}", self.name);
+        PyResult::Ok(())
+    }
+
+    /// Scroll the mouse wheel over this element's center. `delta_x`/`delta_y` count wheel
+    /// notches, each sent as `WHEEL_DELTA` (120) units of wheel data: a positive `delta_y`
+    /// rolls the wheel forward (away from the user), a positive `delta_x` tilts it right.
+    pub fn scroll(&self, delta_x: i32, delta_y: i32) -> PyResult<()> {
+        let Ok(e) = convert_to_ui_element(self) else {
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element not found"));
+        };
+
+        let (x, y) = element_center(&e)?;
+        unsafe {
+            if let Err(e) = SetCursorPos(x, y) {
+                println!("Error moving cursor before scroll: {:?}", e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move cursor for scroll"));
+            }
+            if delta_y != 0 {
+                mouse_event(MOUSEEVENTF_WHEEL, 0, 0, (delta_y * WHEEL_DELTA) as u32, 0);
+            }
+            if delta_x != 0 {
+                mouse_event(MOUSEEVENTF_HWHEEL, 0, 0, (delta_x * WHEEL_DELTA) as u32, 0);
+            }
+        }
+
+        println!("Scrolled delta_x={} delta_y={} over element: {}", delta_x, delta_y, self.name);
+        PyResult::Ok(())
+    }
+
+    /// Drag from this element's center to `target`'s center: press at the source, step
+    /// towards the target over a few intermediate moves, then release over the target.
+    pub fn drag_to(&self, target: &Element) -> PyResult<()> {
+        let Ok(source_e) = convert_to_ui_element(self) else {
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element not found"));
+        };
+        let Ok(target_e) = convert_to_ui_element(target) else {
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Target element not found"));
+        };
+
+        let (start_x, start_y) = element_center(&source_e)?;
+        let (end_x, end_y) = element_center(&target_e)?;
+
+        unsafe {
+            if let Err(e) = SetCursorPos(start_x, start_y) {
+                println!("Error moving cursor to drag start: {:?}", e);
+                return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move cursor to drag start"));
+            }
+            mouse_event(MOUSEEVENTF_LEFTDOWN, 0, 0, 0, 0);
+
+            for step in 1..=DRAG_STEPS {
+                let t = step as f32 / DRAG_STEPS as f32;
+                let x = start_x + ((end_x - start_x) as f32 * t) as i32;
+                let y = start_y + ((end_y - start_y) as f32 * t) as i32;
+                if let Err(e) = SetCursorPos(x, y) {
+                    println!("Error moving cursor during drag: {:?}", e);
+                    mouse_event(MOUSEEVENTF_LEFTUP, 0, 0, 0, 0);
+                    return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move cursor during drag"));
+                }
+                thread::sleep(Duration::from_millis(DRAG_STEP_DELAY_MS));
+            }
+
+            mouse_event(MOUSEEVENTF_LEFTUP, 0, 0, 0, 0);
+        }
+
+        println!("Dragged from element {} to element {}", self.name, target.name);
+        PyResult::Ok(())
+    }
+
+    /// The true composited frame rectangle of this element's top-level window, as
+    /// `(left, top, right, bottom)` in screen coordinates. Queries the DWM extended frame
+    /// bounds (which, unlike plain `GetWindowRect`, consistently excludes the invisible
+    /// drop-shadow margin some themes draw around a window), falling back to
+    /// `GetWindowRect` if the DWM query fails - e.g. for a non-composited or minimized
+    /// window - so callers targeting a title bar or window edge don't drift by the shadow
+    /// offset.
+    pub fn get_window_frame_bounds(&self) -> PyResult<(i32, i32, i32, i32)> {
+        if self.handle == 0 {
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element has no window handle"));
+        }
+
+        let hwnd = window_handle(self);
+        let mut rect = RECT::default();
+
+        unsafe {
+            let dwm_result = DwmGetWindowAttribute(
+                hwnd,
+                DWMWA_EXTENDED_FRAME_BOUNDS,
+                &mut rect as *mut _ as *mut std::ffi::c_void,
+                std::mem::size_of::<RECT>() as u32,
+            );
+
+            if dwm_result.is_err() {
+                if let Err(e) = GetWindowRect(hwnd, &mut rect) {
+                    println!("Error getting window frame bounds: {:?}", e);
+                    return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to get window frame bounds"));
+                }
+            }
+        }
+
+        PyResult::Ok((rect.left, rect.top, rect.right, rect.bottom))
+    }
+
+    /// The client area's top-left offset from its window's frame origin, derived from the
+    /// sizing-frame and caption-bar system metrics - combine with
+    /// `get_window_frame_bounds` to map element-relative coordinates into the real window
+    /// without guessing the frame/shadow offset.
+    pub fn get_client_area_origin(&self) -> PyResult<(i32, i32)> {
+        unsafe {
+            let frame_x = GetSystemMetrics(SM_CXSIZEFRAME);
+            let frame_y = GetSystemMetrics(SM_CYSIZEFRAME);
+            let caption_y = GetSystemMetrics(SM_CYCAPTION);
+            PyResult::Ok((frame_x, frame_y + caption_y))
+        }
+    }
+
+    /// Send literal text as keystrokes to this element.
+    pub fn send_keys(&self, text: String) -> PyResult<()> {
+        if let Ok(e) = convert_to_ui_element(self) {
+            match e.send_keys(&text, 20) {
+                Ok(_) => {
+                    println!("Sent keys '{}' to element: {}", text, self.name);
+                }
+                Err(e) => {
+                    println!("Error sending keys to element: {:?}", e);
+                    return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Send keys failed"));
+                }
             }
         } else {
             return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element not found"));
@@ -91,6 +343,49 @@ impl Element {
 
 }
 
+/// The DPI scale (dpi/96) of the monitor under `(x, y)`, via `MonitorFromPoint` +
+/// `GetDpiForMonitor` - the per-monitor-aware replacement for `ScreenContext`'s single
+/// averaged `screen_scale`.
+fn monitor_scale_at(x: i32, y: i32) -> f32 {
+    unsafe {
+        let hmonitor = MonitorFromPoint(POINT { x, y }, MONITOR_FROM_FLAGS { 0: 2 }); // MONITOR_DEFAULTTONEAREST
+        let mut dpi_x = 0;
+        let mut dpi_y = 0;
+        let _res = GetDpiForMonitor(hmonitor, MONITOR_DPI_TYPE { 0: 0 }, &mut dpi_x, &mut dpi_y);
+        dpi_x as f32 / 96.0
+    }
+}
+
+/// `element`'s stored handle as an `HWND`, for the window-management Win32 calls.
+fn window_handle(element: &Element) -> HWND {
+    HWND(element.handle as *mut _)
+}
+
+/// `element`'s current bounding-rectangle center, in screen coordinates - the point
+/// every click/hover/drag/scroll method above targets. Queried live from `element`
+/// rather than from a `Element`'s cached `bounding_rectangle` snapshot, so a target that
+/// has moved or resized since it was captured is still hit.
+fn element_center(element: &UIElement) -> PyResult<(i32, i32)> {
+    let rect = element.get_bounding_rectangle().map_err(|e| {
+        println!("Error getting live bounding rectangle: {:?}", e);
+        pyo3::exceptions::PyValueError::new_err("Failed to get element bounds")
+    })?;
+    Ok(((rect.get_left() + rect.get_right()) / 2, (rect.get_top() + rect.get_bottom()) / 2))
+}
+
+/// Move the cursor to `(x, y)` and emit `down_flag` followed by `up_flag`.
+fn synthesize_click(x: i32, y: i32, down_flag: windows::Win32::UI::WindowsAndMessaging::MOUSE_EVENT_FLAGS, up_flag: windows::Win32::UI::WindowsAndMessaging::MOUSE_EVENT_FLAGS) -> PyResult<()> {
+    unsafe {
+        if let Err(e) = SetCursorPos(x, y) {
+            println!("Error moving cursor: {:?}", e);
+            return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Failed to move cursor"));
+        }
+        mouse_event(down_flag, 0, 0, 0, 0);
+        mouse_event(up_flag, 0, 0, 0, 0);
+    }
+    PyResult::Ok(())
+}
+
 impl Default for Element {
     fn default() -> Self {
         Element {
@@ -104,10 +399,20 @@ impl Default for Element {
                 right: 0,
                 bottom: 0,
             },
+            scale: 1.0,
         }
     }
 }
 
+impl Element {
+    /// Record the DPI scale of the monitor this element was captured on - not exposed to
+    /// Python, set internally right after construction by `WinDriver::get_ui_element`.
+    fn with_capture_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
 fn convert_to_ui_element(element: &Element) -> Result<UIElement, uiautomation::Error> {
 
     // first try to get the element by runtime id
@@ -120,10 +425,11 @@ fn convert_to_ui_element(element: &Element) -> Result<UIElement, uiautomation::E
         {
             let guard = WINDRIVER.lock().unwrap();
             let windriver = guard.as_ref().ok_or_else(|| uiautomation::Error::new(uiautomation::errors::ERR_NOTFOUND, "WinDriver not initialized"))?;
-            let ui_tree = &windriver.ui_tree;
-            if let Some(ui_element) = get_ui_element_by_xpath(element.get_xpath(), ui_tree) {
+            let ui_tree = windriver.ui_tree.lock().unwrap();
+            if let Some(ui_element) = get_ui_element_by_xpath(element.get_xpath(), &ui_tree) {
                 return Ok(ui_element);
             } else {
+                windriver.needs_update.store(true, Ordering::SeqCst);
                 return Err(uiautomation::Error::new(uiautomation::errors::ERR_NOTFOUND, "could not find element"));
             }
         }
@@ -131,30 +437,58 @@ fn convert_to_ui_element(element: &Element) -> Result<UIElement, uiautomation::E
 
 }
 
+/// Walk the UI tree once on a dedicated worker thread and wait for the result - `get_all_elements`
+/// needs its own thread (UI Automation's COM apartment requirements), but the wait itself is
+/// bounded to this one walk rather than the lifetime of a long-running watcher.
+fn walk_ui_tree() -> PyResult<UITree> {
+    let (tx, rx): (Sender<_>, Receiver<crate::UITree>) = channel();
+    thread::spawn(|| {
+        crate::get_all_elements(tx, None);
+    });
+    rx.recv().map_err(|_| pyo3::exceptions::PyValueError::new_err("UI tree walk worker thread disconnected before sending a result"))
+}
 
+/// Set by `WinDriver::stop_watching` and polled by the background thread `start_watching`
+/// spawns; there is only ever one watcher at a time (mirroring the single global `WINDRIVER`).
+static STOP_WATCHING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(true);
+
+/// The currently running watcher thread, if any - joined by `start_watching` before it
+/// spawns a replacement (and by `stop_watching`), so repeated calls restart the interval
+/// instead of leaking a thread every time.
+static WATCHER_HANDLE: Mutex<Option<thread::JoinHandle<()>>> = Mutex::new(None);
+
+/// Signal `STOP_WATCHING` and join whatever watcher thread is currently registered in
+/// `WATCHER_HANDLE`, if any. A no-op if no watcher is running.
+fn stop_and_join_watcher() {
+    STOP_WATCHING.store(true, Ordering::SeqCst);
+    if let Some(handle) = WATCHER_HANDLE.lock().unwrap().take() {
+        let _ = handle.join();
+    }
+}
 
 #[pyclass]
 #[derive(Debug, Clone)]
 pub struct WinDriver {
     timeout_ms: u64,
-    ui_tree: UITree,
-    needs_update: bool,
+    /// Shared with the copy stored in `WINDRIVER` and with any running watcher thread, so a
+    /// background tree walk landing updates every handle to this driver at once.
+    ui_tree: std::sync::Arc<Mutex<UITree>>,
+    /// Shared the same way as `ui_tree`; flipped to `true` by a failed hit-test/xpath lookup
+    /// and back to `false` whenever a background walk lands. Only `__repr__` reports it: the
+    /// watcher and `refresh` re-walk the tree whether or not it is set.
+    needs_update: std::sync::Arc<std::sync::atomic::AtomicBool>,
 }
 
 #[pymethods]
 impl WinDriver {
     #[new]
     pub fn new(timeout_ms: u64) -> PyResult<Self> {
-        
-        // get the ui tree in a separate thread
-        let (tx, rx): (Sender<_>, Receiver<crate::UITree>) = channel();
-        thread::spawn(|| {
-            crate::get_all_elements(tx, None);
-        });
-        println!("Spawned separate thread to get ui tree");
-        
-        let ui_tree: UITree = rx.recv().unwrap();
-        let driver = WinDriver { timeout_ms, ui_tree, needs_update: false };
+        let ui_tree = walk_ui_tree()?;
+        let driver = WinDriver {
+            timeout_ms,
+            ui_tree: std::sync::Arc::new(Mutex::new(ui_tree)),
+            needs_update: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        };
 
         *WINDRIVER.lock().unwrap() = Some(driver.clone());
 
@@ -162,7 +496,7 @@ impl WinDriver {
     }
 
     pub fn __repr__(&self) -> PyResult<String> {
-        PyResult::Ok(format!("<WinDriver timeout={}>, ui_tree={{object}}, needs_update={}", self.timeout_ms, self.needs_update))
+        PyResult::Ok(format!("<WinDriver timeout={}>, ui_tree={{object}}, needs_update={}", self.timeout_ms, self.needs_update.load(Ordering::SeqCst)))
     }
 
     pub fn __str__(&self) -> PyResult<String> {
@@ -185,12 +519,42 @@ impl WinDriver {
         }
     }
 
-    pub fn get_ui_element(&self, x: i32, y: i32) -> PyResult<Element> {
-    
+    /// Logical (DPI-scaled) coordinates to physical pixels, using the DPI of the monitor
+    /// at `(x, y)` - equivalent physical coordinates may differ per monitor on a
+    /// per-monitor-DPI-aware multi-head setup.
+    pub fn logical_to_physical(&self, x: i32, y: i32) -> PyResult<(i32, i32)> {
+        let scale = monitor_scale_at(x, y);
+        PyResult::Ok(((x as f32 * scale).round() as i32, (y as f32 * scale).round() as i32))
+    }
+
+    /// Physical pixels to logical (DPI-scaled) coordinates, using the DPI of the monitor
+    /// at `(x, y)`.
+    pub fn physical_to_logical(&self, x: i32, y: i32) -> PyResult<(i32, i32)> {
+        let scale = monitor_scale_at(x, y);
+        PyResult::Ok(((x as f32 / scale).round() as i32, (y as f32 / scale).round() as i32))
+    }
+
+    /// Hit-test the UI element at `(x, y)`. `coordinate_space` defaults to `"physical"`;
+    /// pass `"logical"` to have `(x, y)` normalized to physical pixels (via the DPI of the
+    /// monitor under the point) before the bounding-rect hit test - needed on a
+    /// per-monitor-DPI-aware multi-head setup where a flat scale factor would mislocate
+    /// elements on any monitor but the one it was measured on.
+    #[pyo3(signature = (x, y, coordinate_space=None))]
+    pub fn get_ui_element(&self, x: i32, y: i32, coordinate_space: Option<String>) -> PyResult<Element> {
+
+        let is_logical = matches!(coordinate_space.as_deref(), Some("logical"));
+        let (x, y) = if is_logical {
+            let scale = monitor_scale_at(x, y);
+            ((x as f32 * scale).round() as i32, (y as f32 * scale).round() as i32)
+        } else {
+            (x, y)
+        };
+
         let cursor_position = POINT { x, y };
+        let ui_tree = self.ui_tree.lock().unwrap();
 
-        if let Some(ui_element_in_tree) = crate::rectangle::get_point_bounding_rect(&cursor_position, self.ui_tree.get_elements()) {
-            let xpath = self.ui_tree.get_xpath_for_element(ui_element_in_tree.get_tree_index());
+        if let Some(ui_element_in_tree) = crate::rectangle::get_point_bounding_rect(&cursor_position, ui_tree.get_elements()) {
+            let xpath = ui_tree.get_xpath_for_element(ui_element_in_tree.get_tree_index());
             let ui_element_props = ui_element_in_tree.get_element_props();
             let element = Element::new(
                 ui_element_props.name.clone(),
@@ -198,18 +562,21 @@ impl WinDriver {
                 ui_element_props.handle,
                 ui_element_props.runtime_id.clone(),
                 (ui_element_props.bounding_rect.get_left(), ui_element_props.bounding_rect.get_top(), ui_element_props.bounding_rect.get_right(), ui_element_props.bounding_rect.get_bottom())
-            );
+            ).with_capture_scale(monitor_scale_at(x, y));
             return PyResult::Ok(element)
         } else {
+            self.needs_update.store(true, Ordering::SeqCst);
             return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element not found at the given coordinates"))
         }
 
     }
 
     fn get_ui_element_by_xpath(&self, xpath: String) -> PyResult<Element> {
-        
-        let ui_elem = get_element_by_xpath(xpath.clone(), &self.ui_tree);
+
+        let ui_tree = self.ui_tree.lock().unwrap();
+        let ui_elem = get_element_by_xpath(xpath.clone(), &ui_tree);
         if ui_elem.is_none() {
+            self.needs_update.store(true, Ordering::SeqCst);
             return PyResult::Err(pyo3::exceptions::PyValueError::new_err("Element not found"));
         }
         let element = ui_elem.unwrap();
@@ -238,24 +605,61 @@ impl WinDriver {
         PyResult::Ok(result)
     }
 
+    /// Trigger a UI tree walk in the background and return immediately; the new tree lands
+    /// (shared with every handle to this driver, including the one in `WINDRIVER`) once the
+    /// walk completes, instead of blocking the caller for the whole walk.
     fn refresh(&mut self) -> PyResult<()> {
-        // get the ui tree in a separate thread
-        let (tx, rx): (Sender<_>, Receiver<crate::UITree>) = channel();
-        thread::spawn(|| {
-            crate::get_all_elements(tx, None);
+        let ui_tree_handle = self.ui_tree.clone();
+        let needs_update_handle = self.needs_update.clone();
+
+        thread::spawn(move || {
+            match walk_ui_tree() {
+                Ok(ui_tree) => {
+                    *ui_tree_handle.lock().unwrap() = ui_tree;
+                    needs_update_handle.store(false, Ordering::SeqCst);
+                    println!("Refreshed ui tree in the background");
+                }
+                Err(e) => println!("Background ui tree refresh failed: {:?}", e),
+            }
         });
-        println!("Spawned separate thread to refresh ui tree");
-        
-        let ui_tree: UITree = rx.recv().unwrap();
-        
-        self.ui_tree = ui_tree;
-        self.needs_update = false;
-        
-        {
-            *WINDRIVER.lock().unwrap() = Some(self.clone());
-        }
 
         PyResult::Ok(())
     }
+
+    /// Start a background thread that re-walks the UI tree every `interval_ms` and swaps it
+    /// in atomically, so the cached tree stays roughly current without the caller ever
+    /// blocking on a walk. Only one watcher runs at a time; a second call restarts the
+    /// interval rather than stacking threads.
+    pub fn start_watching(&self, interval_ms: u64) -> PyResult<()> {
+        stop_and_join_watcher();
+        STOP_WATCHING.store(false, Ordering::SeqCst);
+
+        let ui_tree_handle = self.ui_tree.clone();
+        let needs_update_handle = self.needs_update.clone();
+
+        let handle = thread::spawn(move || {
+            while !STOP_WATCHING.load(Ordering::SeqCst) {
+                match walk_ui_tree() {
+                    Ok(ui_tree) => {
+                        *ui_tree_handle.lock().unwrap() = ui_tree;
+                        needs_update_handle.store(false, Ordering::SeqCst);
+                    }
+                    Err(e) => println!("Background ui tree watcher walk failed: {:?}", e),
+                }
+                thread::sleep(Duration::from_millis(interval_ms));
+            }
+        });
+        *WATCHER_HANDLE.lock().unwrap() = Some(handle);
+
+        println!("Started watching for ui tree updates every {}ms", interval_ms);
+        PyResult::Ok(())
+    }
+
+    /// Stop a watcher started by `start_watching`; a no-op if none is running.
+    pub fn stop_watching(&self) -> PyResult<()> {
+        stop_and_join_watcher();
+        println!("Stopped watching for ui tree updates");
+        PyResult::Ok(())
+    }
 }
 
@@ -1,11 +1,11 @@
 // things required for the XPath generation
-// use crate::bindings;
-// use winapi::um::winuser::SetProcessDPIAware;
+use uiautomation::types::Point;
+use uiautomation::{UIAutomation, UIElement, UITreeWalker};
 
 // things required for the XPath parsing
 use winnow::{
-    ascii::alpha1,
-    combinator::{delimited, separated_pair},
+    ascii::{alpha1, digit1},
+    combinator::{alt, delimited, separated_pair},
     prelude::*,
     token::take_until,
     Result,
@@ -16,10 +16,22 @@ use crate::printfmt;
 
 
 // region: XPath parsing
+
+/// How an `[@Key=...]`-style predicate compares an attribute's value against the one
+/// parsed out of the xpath. `Eq` is what every predicate produced before this grammar
+/// grew `contains()`/`startswith()`, so it stays the default for a plain `[@Key="value"]`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PredicateOp {
+    Eq,
+    Contains,
+    StartsWith,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Attribute<'a> {
     pub key: &'a str,
     pub value: &'a str,
+    pub op: PredicateOp,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -28,8 +40,19 @@ pub struct XpathElement<'a> {
     pub classname: Option<&'a str>,
     pub name: Option<&'a str>,
     pub automationid: Option<&'a str>,
-    // pub attributes: Vec<Attribute<'a>>,
+    pub framework_id: Option<&'a str>,
+    pub localized_control_type: Option<&'a str>,
+    pub runtime_id: Option<&'a str>,
+    /// Every `[@Key=...]`/`[contains(@Key,...)]`/`[startswith(@Key,...)]` predicate
+    /// parsed for this element - lets a caller generalize beyond the named fields above
+    /// to arbitrary UIA properties (`HelpText`, `AccessKey`, ...) without re-parsing the
+    /// xpath.
+    pub attributes: Vec<Attribute<'a>>,
     pub attribute_count: usize,
+    /// The `[n]` positional index, if this step has one - `Button[2]` picks the second
+    /// `Button` among otherwise-matching siblings rather than requiring a predicate that
+    /// disambiguates it.
+    pub index: Option<usize>,
 }
 
 impl Default for XpathElement<'_> {
@@ -39,8 +62,12 @@ impl Default for XpathElement<'_> {
             classname: None,
             name: None,
             automationid: None,
-            // attributes: Vec::new(),
+            framework_id: None,
+            localized_control_type: None,
+            runtime_id: None,
+            attributes: Vec::new(),
             attribute_count: 0,
+            index: None,
         }
     }
 }
@@ -51,7 +78,7 @@ fn parse_at_identifier<'a>(input: &mut &'a str) -> Result<&'a str> {
 }
 
 fn parse_element_control_type<'a>(input: &mut &'a str) -> Result<&'a str> {
-    alpha1.parse_next(input)
+    alt(("*", alpha1)).parse_next(input)
 }
 
 fn parse_attribute_value<'a>(input: &mut &'a str) -> Result<&'a str> {
@@ -62,57 +89,121 @@ fn parse_attribute_value<'a>(input: &mut &'a str) -> Result<&'a str> {
     ).parse_next(input)
 }
 
-fn parse_attribute<'a>(input: &mut &'a str) -> Result<Attribute<'a>> {
+/// `[@Key="value"]` - the exact-match predicate every prior version of this grammar
+/// understood.
+fn parse_eq_predicate<'a>(input: &mut &'a str) -> Result<Attribute<'a>> {
+    let (key, value) = separated_pair(
+        parse_at_identifier,
+        "=",
+        parse_attribute_value
+    ).parse_next(input)?;
+
+    Ok(Attribute { key, value, op: PredicateOp::Eq })
+}
+
+/// `contains(@Key,"value")` / `startswith(@Key,"value")` - a function-style predicate
+/// for selectors that need to tolerate a dynamic `Name`/`AutomationId` instead of
+/// matching it byte for byte.
+fn parse_function_predicate<'a>(input: &mut &'a str) -> Result<Attribute<'a>> {
+    let function_name = alt(("contains", "startswith")).parse_next(input)?;
     let (key, value) = delimited(
+        "(",
+        separated_pair(parse_at_identifier, ",", parse_attribute_value),
+        ")",
+    ).parse_next(input)?;
+
+    let op = match function_name {
+        "contains" => PredicateOp::Contains,
+        _ => PredicateOp::StartsWith,
+    };
+    Ok(Attribute { key, value, op })
+}
+
+fn parse_attribute<'a>(input: &mut &'a str) -> Result<Attribute<'a>> {
+    delimited(
         "[",
-        separated_pair(
-            parse_at_identifier,
-            "=",
-            parse_attribute_value
-        ),
+        alt((parse_function_predicate, parse_eq_predicate)),
         "]",
-    ).parse_next(input)?;
-    
-    Ok(Attribute { key, value })
+    ).parse_next(input)
+}
+
+/// `[n]` - a positional index step, tried once the content inside `[...]` fails to
+/// parse as an attribute predicate.
+fn parse_index_step(input: &mut &str) -> Result<usize> {
+    let digits = delimited("[", digit1, "]").parse_next(input)?;
+    Ok(digits.parse::<usize>().unwrap_or(0))
 }
 
 fn parse_element<'a>(input: &mut &'a str) -> Result<XpathElement<'a>> {
     printfmt!("Parsing element: {}", input);
-    let control_type = parse_element_control_type(input)?;    
+    let control_type = parse_element_control_type(input)?;
     printfmt!("Control type: {}", control_type);
     let mut attribute_count = 0;
     let mut classname: Option<&str> = None;
     let mut name: Option<&str> = None;
     let mut automationid: Option<&str> = None;
-    // let mut attributes = Vec::new();
-    
+    let mut framework_id: Option<&str> = None;
+    let mut localized_control_type: Option<&str> = None;
+    let mut runtime_id: Option<&str> = None;
+    let mut attributes = Vec::new();
+    let mut index: Option<usize> = None;
+
     printfmt!("Parsing attributes for element: {}", input);
-    while let Ok(attr) = parse_attribute(input) {
-        printfmt!("Parsed attribute: {:?}", attr);
-        // attributes.push(attr);
-        match attr.key {
-            "ClassName" => {
-                // println!("ClassName: {}", attr.value);
-                classname = Some(attr.value);
-                attribute_count += 1;
-            },
-            "Name" => {
-                // println!("Name: {}", attr.value);
-                name = Some(attr.value);
-                attribute_count += 1;
-            },
-            "AutomationId" => {
-                // println!("AutomationId: {}", attr.value);
-                automationid = Some(attr.value);
-                attribute_count += 1;
-            },
-            _ => {}
+    loop {
+        if let Ok(attr) = parse_attribute(input) {
+            printfmt!("Parsed attribute: {:?}", attr);
+            attributes.push(attr.clone());
+            match attr.key {
+                "ClassName" => {
+                    classname = Some(attr.value);
+                    attribute_count += 1;
+                },
+                "Name" => {
+                    name = Some(attr.value);
+                    attribute_count += 1;
+                },
+                "AutomationId" => {
+                    automationid = Some(attr.value);
+                    attribute_count += 1;
+                },
+                "FrameworkId" => {
+                    framework_id = Some(attr.value);
+                    attribute_count += 1;
+                },
+                "LocalizedControlType" => {
+                    localized_control_type = Some(attr.value);
+                    attribute_count += 1;
+                },
+                "RuntimeId" => {
+                    runtime_id = Some(attr.value);
+                    attribute_count += 1;
+                },
+                _ => {}
+            }
+            continue;
+        }
+
+        if let Ok(parsed_index) = parse_index_step(input) {
+            printfmt!("Parsed index: {}", parsed_index);
+            index = Some(parsed_index);
+            continue;
         }
-        
+
+        break;
     }
-    
-    // let attribute_count = attributes.len();
-    Ok(XpathElement { control_type, classname, name, automationid, attribute_count})
+
+    Ok(XpathElement {
+        control_type,
+        classname,
+        name,
+        automationid,
+        framework_id,
+        localized_control_type,
+        runtime_id,
+        attributes,
+        attribute_count,
+        index,
+    })
 }
 
 
@@ -134,4 +225,126 @@ pub fn get_path_to_element<'a>(input: &mut &'a str) -> Result<Vec<XpathElement<'
     
     Ok(path_to_element)
 }
-// endregion: XPath parsing
\ No newline at end of file
+// endregion: XPath parsing
+
+// region: XPath generation
+
+/// The UIA identity of one ancestor on the path from the desktop root down to a target
+/// element - the owned, generation-side counterpart of the borrowed [`XpathElement`]
+/// parsed out of an xpath string.
+struct XpathAncestor {
+    control_type: String,
+    classname: String,
+    name: String,
+    automation_id: String,
+}
+
+impl From<&UIElement> for XpathAncestor {
+    fn from(element: &UIElement) -> Self {
+        XpathAncestor {
+            control_type: element.get_control_type().map(|t| t.to_string()).unwrap_or_default(),
+            classname: element.get_classname().unwrap_or_default(),
+            name: element.get_name().unwrap_or_default(),
+            automation_id: element.get_automation_id().unwrap_or_default(),
+        }
+    }
+}
+
+/// Escape a predicate value the same way `parse_attribute_value` expects it: any literal
+/// `"` is backslash-escaped so it can never be mistaken for the `\"` delimiter.
+fn escape_attribute_value(value: &str) -> String {
+    value.replace('"', "\\\"")
+}
+
+/// Render one ancestor as the step `get_path_to_element` parses back: the control type,
+/// then - preferring `AutomationId` when it's set, falling back to `ClassName` and/or
+/// `Name` - the minimal predicate set that identifies it, then an optional trailing
+/// `[n]` positional index when those predicates don't already disambiguate it from a
+/// sibling.
+fn render_xpath_step(ancestor: &XpathAncestor, sibling_index: Option<usize>) -> String {
+    let mut step = ancestor.control_type.clone();
+
+    if !ancestor.automation_id.is_empty() {
+        step.push_str(&format!("[@AutomationId=\\\"{}\\\"]", escape_attribute_value(&ancestor.automation_id)));
+    } else {
+        if !ancestor.classname.is_empty() {
+            step.push_str(&format!("[@ClassName=\\\"{}\\\"]", escape_attribute_value(&ancestor.classname)));
+        }
+        if !ancestor.name.is_empty() {
+            step.push_str(&format!("[@Name=\\\"{}\\\"]", escape_attribute_value(&ancestor.name)));
+        }
+    }
+
+    if let Some(index) = sibling_index {
+        step.push_str(&format!("[{}]", index));
+    }
+
+    step
+}
+
+/// `current`'s 1-based position among the children of `parent` that would render to the
+/// same `step` (ignoring any index), or `None` if `step` is already unique among them -
+/// the case `render_xpath_step` doesn't need a positional index for.
+fn sibling_index(walker: &UITreeWalker, parent: &UIElement, current: &UIElement, step: &str) -> Option<usize> {
+    let current_id = current.get_runtime_id().unwrap_or_default();
+    let mut matching_ids: Vec<Vec<i32>> = Vec::new();
+
+    let mut sibling = walker.get_first_child(parent).ok()?;
+    loop {
+        if render_xpath_step(&XpathAncestor::from(&sibling), None) == step {
+            matching_ids.push(sibling.get_runtime_id().unwrap_or_default());
+        }
+        sibling = match walker.get_next_sibling(&sibling) {
+            Ok(next) => next,
+            Err(_) => break,
+        };
+    }
+
+    if matching_ids.len() <= 1 {
+        return None;
+    }
+    matching_ids.iter().position(|id| *id == current_id).map(|position| position + 1)
+}
+
+/// Build the canonical xpath for whatever element sits at screen point `(x, y)`, in
+/// exactly the format `get_path_to_element` parses: fills in the generator this module's
+/// "things required for the XPath generation" banner promised but never implemented.
+///
+/// Walks up the control-view ancestor chain from the resolved element to the desktop
+/// root, rendering one step per ancestor (closest to the root first) and adding a
+/// positional index wherever a sibling would otherwise render identically - then
+/// prepends a placeholder step for the desktop root itself, matching the leading
+/// `/<root>/` that `get_path_to_element` always skips. Returns an empty string if the
+/// point doesn't resolve to an element.
+pub fn generate_xpath(x: i32, y: i32) -> String {
+    let Ok(automation) = UIAutomation::new() else { return String::new() };
+    let Ok(root) = automation.get_root_element() else { return String::new() };
+    let Ok(walker) = automation.get_control_view_walker() else { return String::new() };
+    let Ok(mut current) = automation.element_from_point(Point::new(x, y)) else { return String::new() };
+
+    let root_id = root.get_runtime_id().unwrap_or_default();
+    let mut steps: Vec<String> = Vec::new();
+
+    loop {
+        if current.get_runtime_id().unwrap_or_default() == root_id {
+            break;
+        }
+
+        let ancestor = XpathAncestor::from(&current);
+        let step_without_index = render_xpath_step(&ancestor, None);
+
+        let Ok(parent) = walker.get_parent(&current) else {
+            steps.push(step_without_index);
+            break;
+        };
+        let index = sibling_index(&walker, &parent, &current, &step_without_index);
+        steps.push(render_xpath_step(&ancestor, index));
+
+        current = parent;
+    }
+    steps.reverse();
+
+    let root_step = render_xpath_step(&XpathAncestor::from(&root), None);
+    std::iter::once(root_step).chain(steps).fold(String::new(), |path, step| path + "/" + &step)
+}
+// endregion: XPath generation
\ No newline at end of file